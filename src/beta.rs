@@ -5,8 +5,13 @@
 use crate::primitive::Primitive;
 use crate::Gamma;
 
+mod toms708;
+
 /// Beta functions.
-pub trait Beta {
+pub trait Beta
+where
+    Self: Sized,
+{
     /// Compute the regularized incomplete beta function.
     ///
     /// `ln_beta` should be precomputed as `p.ln_beta(q)`.
@@ -15,11 +20,67 @@ pub trait Beta {
     /// algorithm was published in Applied Statistics and is known as [Algorithm AS 63][2] and
     /// [Algorithm AS 109][3].
     ///
+    /// When p and q are large or differ by orders of magnitude, the function switches to
+    /// [Algorithm 708][4] by A. R. DiDonato and A. H. Morris, which relies on asymptotic
+    /// expansions and continued fractions in these regimes. Algorithm 708 evaluates the beta
+    /// function on its own, so `ln_beta` is not used in this case.
+    ///
     /// [1]: http://people.sc.fsu.edu/~jburkardt/c_src/asa109/asa109.html
     /// [2]: http://www.jstor.org/stable/2346797
     /// [3]: http://www.jstor.org/stable/2346887
+    /// [4]: https://dl.acm.org/doi/10.1145/131766.131776
     fn inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self;
 
+    /// Compute the natural logarithm of the regularized incomplete beta function.
+    ///
+    /// `ln_beta` should be precomputed as `p.ln_beta(q)`.
+    ///
+    /// The prefactor of the series used by `inc_beta` is kept in log space, so the result remains
+    /// finite when the function itself underflows. As in `inc_beta`, `ln_beta` is not used when
+    /// Algorithm 708 applies and neither tail is close to underflow.
+    fn ln_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self;
+
+    /// Compute the natural logarithm of the complement of the regularized incomplete beta
+    /// function, that is, ln(1 - I(x, p, q)).
+    ///
+    /// `ln_beta` should be precomputed as `p.ln_beta(q)`.
+    fn ln_compl_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self;
+
+    /// Compute the incomplete beta function without regularization.
+    ///
+    /// The function computes B(x, a, b), which is the integral of t^(a - 1) (1 - t)^(b - 1) from
+    /// zero to x. For a ≤ 0 or b ≤ 0, where the integral diverges, the result is defined by
+    /// analytic continuation,
+    ///
+    /// ```text
+    ///                x^a (1 - x)^b
+    /// B(x, a, b) = ----------------- ₂F₁(1, a + b; a + 1; x),
+    ///                      a
+    /// ```
+    ///
+    /// and it is undefined, and hence not a number, if a is zero or a negative integer. When both
+    /// parameters are positive, the function is computed via `ln_inc_beta` in log space.
+    /// Otherwise, the above series is used for x ≤ 1/2 and the reflection
+    /// B(x, a, b) = B(a, b) - B(1 - x, b, a) for x > 1/2 where B(a, b) is finite. For x > 1/2 and
+    /// b a non-positive integer, the series is used as long as it converges in a moderate number
+    /// of terms; closer to one, the function is computed from a closed form for b = 0 that
+    /// involves ln(1 - x) and a series in 1 - x, followed by a recurrence in b.
+    fn unreg_inc_beta(self, a: Self, b: Self) -> Self;
+
+    /// Compute the regularized incomplete beta function and its partial derivatives.
+    ///
+    /// `ln_beta` should be precomputed as `p.ln_beta(q)`.
+    ///
+    /// The function returns I(x, p, q) together with ∂I/∂x, ∂I/∂p, and ∂I/∂q. The derivatives
+    /// with respect to p and q are obtained by differentiating the series of Algorithm AS 63 term
+    /// by term along with the prefactor, which involves the digamma function. The prefactors
+    /// of the form x^p (1 - x)^q / B(p, q) are computed as in [Algorithm 708][1], which keeps them
+    /// accurate for large p and q. However, the number of terms of the series grows in proportion
+    /// to p + q.
+    ///
+    /// [1]: https://dl.acm.org/doi/10.1145/131766.131776
+    fn inc_beta_grad(self, p: Self, q: Self, ln_beta: Self) -> (Self, Self, Self, Self);
+
     /// Compute the inverse of the regularized incomplete beta function.
     ///
     /// `ln_beta` should be precomputed as `p.ln_beta(q)`.
@@ -28,18 +89,311 @@ pub trait Beta {
     /// algorithm was published in Applied Statistics and is known as [Algorithm AS 64][2] and
     /// [Algorithm AS 109][3].
     ///
+    /// If the Newton–Raphson iteration fails to converge to full relative accuracy, which can
+    /// happen for extreme parameters, the solution is found by bisection over the bracket
+    /// established by the iteration.
+    ///
     /// [1]: http://people.sc.fsu.edu/~jburkardt/c_src/asa109/asa109.html
     /// [2]: http://www.jstor.org/stable/2346798
     /// [3]: http://www.jstor.org/stable/2346887
     fn inv_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self;
 
+    /// Compute the inverse of the regularized incomplete beta function and report convergence.
+    ///
+    /// The function is the same as `inv_inc_beta` but also reports the number of iterations
+    /// performed, each of which evaluates the incomplete beta function once, and whether the
+    /// solution has converged.
+    fn inv_inc_beta_with_convergence(self, p: Self, q: Self, ln_beta: Self) -> (Self, Convergence);
+
+    /// Compute the inverse of the complement of the regularized incomplete beta function.
+    ///
+    /// `ln_beta` should be precomputed as `p.ln_beta(q)`.
+    ///
+    /// The function finds x such that 1 - I(x, p, q) = α where α is `self`. Unlike passing 1 - α
+    /// to `inv_inc_beta`, small upper-tail probabilities are not lost to rounding.
+    fn inv_compl_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self;
+
+    /// Compute the inverse of the regularized incomplete beta function given the natural
+    /// logarithm of the probability.
+    ///
+    /// `ln_beta` should be precomputed as `p.ln_beta(q)`.
+    ///
+    /// The function finds x such that ln I(x, p, q) = ln α where ln α is `self`, which allows for
+    /// probabilities below the smallest representable number. The solution is refined by Newton’s
+    /// method applied to `ln_inc_beta` with respect to ln x.
+    fn inv_ln_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self;
+
+    /// Compute the inverse of the regularized incomplete beta function with respect to the first
+    /// shape parameter.
+    ///
+    /// The function finds p such that I(x, p, q) = α where α is `self`. Since I decreases
+    /// monotonically in p, the root is bracketed by geometric expansion over (0, ∞) and then
+    /// refined by a combination of geometric bisection and the Illinois method.
+    fn inv_inc_beta_p(self, x: Self, q: Self) -> Self;
+
+    /// Compute the inverse of the regularized incomplete beta function with respect to the second
+    /// shape parameter.
+    ///
+    /// The function finds q such that I(x, p, q) = α where α is `self`. Since I increases
+    /// monotonically in q, the root is bracketed by geometric expansion over (0, ∞) and then
+    /// refined by a combination of geometric bisection and the Illinois method.
+    fn inv_inc_beta_q(self, x: Self, p: Self) -> Self;
+
     /// Compute the natural logarithm of the beta function.
     fn ln_beta(self, other: Self) -> Self;
+
+    /// Compute the natural logarithm of the multivariate beta function.
+    ///
+    /// The function computes ln B(α) = Σ ln Γ(αᵢ) - ln Γ(Σ αᵢ), which is the normalizing constant
+    /// of the Dirichlet distribution. It is evaluated as a sum of bivariate terms
+    /// ln B(α₁ + ⋯ + αᵢ₋₁, αᵢ), each computed by [Algorithm 708][1], which avoids the cancellation
+    /// that occurs when one of the parameters dominates the others. If `alpha` is empty, the
+    /// result is not a number.
+    ///
+    /// [1]: https://dl.acm.org/doi/10.1145/131766.131776
+    fn ln_multi_beta(alpha: &[Self]) -> Self;
+
+    /// Compute the natural logarithm of the multivariate beta function and its gradient.
+    ///
+    /// The partial derivatives ψ(αᵢ) - ψ(Σ αᵢ) are written to `gradient`. Each difference of the
+    /// digamma function is computed directly rather than by subtraction.
+    ///
+    /// The function panics if `gradient` and `alpha` differ in length.
+    fn ln_multi_beta_grad(alpha: &[Self], gradient: &mut [Self]) -> Self;
+}
+
+/// Convergence of an iterative method.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Convergence {
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// The indicator of whether the desired accuracy has been attained.
+    pub converged: bool,
+}
+
+// Find the root of an increasing function on (0, ∞).
+macro_rules! solve_shape {
+    ($kind:ident, $function:expr) => {{
+        const LIMIT: $kind = 1e30;
+        const MAX_ITERATIONS: usize = 1000;
+
+        let function = $function;
+
+        let mut lo: $kind = 1.0;
+        let mut hi: $kind = 1.0;
+        let mut f_lo = function(lo);
+        let mut f_hi = f_lo;
+        if f_lo == 0.0 {
+            return lo;
+        }
+        if f_lo < 0.0 {
+            while f_hi < 0.0 {
+                lo = hi;
+                f_lo = f_hi;
+                if hi >= LIMIT {
+                    return <$kind>::INFINITY;
+                }
+                hi *= 4.0;
+                f_hi = function(hi);
+            }
+        } else {
+            while f_lo > 0.0 {
+                hi = lo;
+                f_hi = f_lo;
+                if lo <= 1.0 / LIMIT {
+                    return 0.0;
+                }
+                lo /= 4.0;
+                f_lo = function(lo);
+            }
+        }
+        if f_lo == 0.0 {
+            return lo;
+        }
+        if f_hi == 0.0 {
+            return hi;
+        }
+
+        let mut side = 0;
+        for _ in 0..MAX_ITERATIONS {
+            if hi - lo <= <$kind>::EPSILON * hi {
+                break;
+            }
+            let mut c = if hi > 4.0 * lo {
+                (lo * hi).sqrt()
+            } else {
+                hi - f_hi * (hi - lo) / (f_hi - f_lo)
+            };
+            if !(c > lo && c < hi) {
+                c = 0.5 * (lo + hi);
+            }
+            let f_c = function(c);
+            if f_c == 0.0 {
+                return c;
+            }
+            if f_c < 0.0 {
+                lo = c;
+                f_lo = f_c;
+                if side == -1 {
+                    f_hi *= 0.5;
+                }
+                side = -1;
+            } else {
+                hi = c;
+                f_hi = f_c;
+                if side == 1 {
+                    f_lo *= 0.5;
+                }
+                side = 1;
+            }
+        }
+        0.5 * (lo + hi)
+    }};
+}
+
+// Evaluate the series of Algorithm AS 63 without its prefactor. The function returns whether the
+// parameters were flipped, the possibly flipped p, q, x, and 1 - x, and the value of the series.
+macro_rules! series {
+    ($kind:ident, $x:expr, $p:expr, $q:expr) => {{
+        const ACU: $kind = 0.1e-14;
+
+        let x: $kind = $x;
+        let mut p: $kind = $p;
+        let mut q: $kind = $q;
+        let mut psq = p + q;
+
+        let pbase;
+        let qbase;
+
+        let mut temp;
+
+        let flip = p < psq * x;
+        if flip {
+            pbase = 1.0 - x;
+            qbase = x;
+            temp = q;
+            q = p;
+            p = temp;
+        } else {
+            pbase = x;
+            qbase = 1.0 - x;
+        }
+
+        let mut term = 1.0;
+        let mut ai = 1.0;
+
+        let mut rx;
+        let mut ns = (q + qbase * psq) as isize;
+        if ns == 0 {
+            rx = pbase;
+        } else {
+            rx = pbase / qbase;
+        }
+
+        let mut a = 1.0;
+        temp = q - ai;
+
+        loop {
+            term = term * temp * rx / (p + ai);
+
+            a += term;
+
+            temp = if term < 0.0 { -term } else { term };
+            if temp <= ACU && temp <= ACU * a {
+                break;
+            }
+
+            ai += 1.0;
+            ns -= 1;
+
+            if 0 < ns {
+                temp = q - ai;
+            } else if ns == 0 {
+                temp = q - ai;
+                rx = pbase;
+            } else {
+                temp = psq;
+                psq += 1.0;
+            }
+        }
+
+        (flip, p, q, pbase, qbase, a)
+    }};
+}
+
+// Compute ln I(x, p, q) and ln(1 - I(x, p, q)) keeping the prefactor in log space.
+fn ln_inc_beta(x: f64, p: f64, q: f64, ln_beta: f64) -> (f64, f64) {
+    debug_assert!(x >= 0.0 && x <= 1.0 && p > 0.0 && q > 0.0);
+
+    if x == 0.0 {
+        return (f64::NEG_INFINITY, 0.0);
+    }
+    if x == 1.0 {
+        return (0.0, f64::NEG_INFINITY);
+    }
+
+    if p + q > 30.0 || p.max(q) > 1e3 * p.min(q) {
+        // Algorithm 708 is used unless the smaller tail is too close to underflow, in which case
+        // the series is accurate enough in log space and converges rapidly.
+        let (w, w1) = toms708::bratio(p, q, x, 1.0 - x);
+        if w.min(w1) > f64::MIN_POSITIVE / f64::EPSILON {
+            return if w < w1 {
+                (w.ln(), (-w).ln_1p())
+            } else {
+                ((-w1).ln_1p(), w1.ln())
+            };
+        }
+    }
+
+    let (flip, p, q, pbase, qbase, a) = series!(f64, x, p, q);
+    let ln_a = a.ln() + p * pbase.ln() + (q - 1.0) * qbase.ln() - ln_beta - p.ln();
+    let ln_1m_a = if ln_a < -core::f64::consts::LN_2 {
+        (-ln_a.exp()).ln_1p()
+    } else {
+        (-ln_a.exp_m1()).ln()
+    };
+
+    if flip {
+        (ln_1m_a, ln_a)
+    } else {
+        (ln_a, ln_1m_a)
+    }
+}
+
+// Compute ψ(a + d) - ψ(a) for a > 0 and d ≥ 0 without cancellation.
+macro_rules! digamma_increment {
+    ($kind:ident, $a:expr, $d:expr) => {{
+        let (mut x, d): ($kind, $kind) = ($a, $d);
+        let mut sum = 0.0;
+        while x <= 8.0 {
+            sum += d / (x * (x + d));
+            x += 1.0;
+        }
+        // The asymptotic expansion used for the digamma function is subtracted term by term.
+        const COEFFICIENTS: [$kind; 8] = [
+            1.0 / 12.0,
+            -1.0 / 120.0,
+            1.0 / 252.0,
+            -1.0 / 240.0,
+            5.0 / 660.0,
+            -691.0 / 32760.0,
+            1.0 / 12.0,
+            -3617.0 / 8160.0,
+        ];
+        let r = (d / x).ln_1p();
+        let q2 = (x * x).recip();
+        let mut power = 1.0;
+        for (k, &c) in COEFFICIENTS.iter().enumerate() {
+            power *= q2;
+            sum -= c * power * (-2.0 * (k + 1) as $kind * r).exp_m1();
+        }
+        sum + r + d / (2.0 * x * (x + d))
+    }};
 }
 
 #[rustfmt::skip]
 macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
-    fn inc_beta(self, mut p: Self, mut q: Self, ln_beta: Self) -> Self {
+    fn inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self {
         // Algorithm AS 63
         // http://www.jstor.org/stable/2346797
         //
@@ -69,8 +423,6 @@ macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
         // Soper (1921) demonstrated that the expansion of I(x, p, q) by “parts” and “raising p”
         // method as described above converges more rapidly than any other series expansions.
 
-        const ACU: $kind = 0.1e-14;
-
         let x = self;
         debug_assert!(x >= 0.0 && x <= 1.0 && p > 0.0 && q > 0.0);
 
@@ -81,75 +433,207 @@ macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
             return 1.0;
         }
 
-        let mut psq = p + q;
+        // The series converges slowly and accumulates rounding errors when p and q are large or
+        // differ by orders of magnitude, in which case Algorithm 708 is used instead.
+        if p + q > 30.0 || p.max(q) > 1e3 * p.min(q) {
+            return toms708::bratio(p as f64, q as f64, x as f64, 1.0 - x as f64).0 as $kind;
+        }
 
-        let pbase;
-        let qbase;
+        let (flip, p, q, pbase, qbase, mut a) = series!($kind, x, p, q);
 
-        let mut temp;
+        // Remark AS R19 and Algorithm AS 109
+        // http://www.jstor.org/stable/2346887
+        a = a * (p * pbase.ln() + (q - 1.0) * qbase.ln() - ln_beta).exp() / p;
 
-        let flip = p < psq * x;
         if flip {
-            pbase = 1.0 - x;
-            qbase = x;
-            temp = q;
-            q = p;
-            p = temp;
+            1.0 - a
         } else {
-            pbase = x;
-            qbase = 1.0 - x;
+            a
         }
+    }
 
-        let mut term = 1.0;
-        let mut ai = 1.0;
+    fn ln_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self {
+        ln_inc_beta(self as f64, p as f64, q as f64, ln_beta as f64).0 as $kind
+    }
 
-        let mut rx;
-        let mut ns = (q + qbase * psq) as isize;
-        if ns == 0 {
-            rx = pbase;
+    fn ln_compl_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self {
+        ln_inc_beta(self as f64, p as f64, q as f64, ln_beta as f64).1 as $kind
+    }
+
+    fn unreg_inc_beta(self, a: Self, b: Self) -> Self {
+        let x = self;
+        debug_assert!(x >= 0.0 && x <= 1.0);
+
+        if a > 0.0 && b > 0.0 {
+            if x == 0.0 {
+                return 0.0;
+            }
+            let ln_beta = a.ln_beta(b);
+            return (x.ln_inc_beta(a, b, ln_beta) + ln_beta).exp();
+        }
+        if a <= 0.0 && a == a.floor() {
+            return <$kind>::NAN;
+        }
+        if x == 1.0 && b <= 0.0 {
+            return <$kind>::INFINITY;
+        }
+
+        // The series converges geometrically at rate x once n exceeds the parameters, which takes
+        // well under `limit` terms wherever it is used below.
+        let limit = 1e3 + a.abs() + b.abs();
+        let series = |x: $kind, a: $kind, b: $kind| {
+            let mut sum = 1.0;
+            let mut term = 1.0;
+            let mut n = 1.0;
+            loop {
+                term *= (a + b + n - 1.0) / (a + n) * x;
+                sum += term;
+                if (n > -a && term.abs() <= <$kind>::EPSILON * sum.abs()) || n > limit {
+                    break;
+                }
+                n += 1.0;
+            }
+            (a * x.ln() + b * (1.0 - x).ln()).exp() / a * sum
+        };
+
+        let pole = b <= 0.0 && b == b.floor();
+        if x <= 0.5 || (pole && (1.0 - x) * limit > 40.0) {
+            series(x, a, b)
+        } else if pole {
+            // The reflection breaks down as B(a, b) has a pole, and the series in x would take too
+            // many terms. For b = 0, the limit of B(a, ε) - B(1 - x, ε, a) as ε → 0 gives
+            //
+            // B(x, a, 0) = -γ - ψ(a) - ln(1 - x) - Σ (1 - a)ₙ (1 - x)ⁿ / (n n!),
+            //
+            // and the recurrence b B(x, a, b) = (a + b) B(x, a, b + 1) - xᵃ (1 - x)ᵇ steps down
+            // to the actual b.
+            const EULER_MASCHERONI: $kind = 0.577215664901532860606512090082;
+            let y = 1.0 - x;
+            let mut sum = 0.0;
+            let mut term = 1.0;
+            let mut n = 1.0;
+            loop {
+                term *= (n - a) / n * y;
+                sum += term / n;
+                if (term / n).abs() <= <$kind>::EPSILON * sum.abs() || n > limit {
+                    break;
+                }
+                n += 1.0;
+            }
+            let mut value = -EULER_MASCHERONI - a.digamma() - y.ln() - sum;
+            let mut c = 0.0;
+            while c > b {
+                c -= 1.0;
+                value = ((a + c) * value - (a * x.ln() + c * y.ln()).exp()) / c;
+            }
+            value
         } else {
-            rx = pbase / qbase;
+            let (ln_gamma_a, sign_a) = a.ln_gamma();
+            let (ln_gamma_b, sign_b) = b.ln_gamma();
+            let (ln_gamma_ab, sign_ab) = (a + b).ln_gamma();
+            let beta = (sign_a * sign_b * sign_ab) as $kind
+                * (ln_gamma_a + ln_gamma_b - ln_gamma_ab).exp();
+            beta - series(1.0 - x, b, a)
         }
+    }
 
-        let mut a = 1.0;
-        temp = q - ai;
+    fn inc_beta_grad(self, p: Self, q: Self, ln_beta: Self) -> (Self, Self, Self, Self) {
+        const ACU: $kind = 0.1e-14;
+
+        let x = self;
+        debug_assert!(x >= 0.0 && x <= 1.0 && p > 0.0 && q > 0.0);
+
+        let value = x.inc_beta(p, q, ln_beta);
+        if x == 0.0 || x == 1.0 {
+            let dx = if (x == 0.0 && p == 1.0) || (x == 1.0 && q == 1.0) {
+                (-ln_beta).exp()
+            } else if (x == 0.0 && p < 1.0) || (x == 1.0 && q < 1.0) {
+                <$kind>::INFINITY
+            } else {
+                0.0
+            };
+            return (value, dx, 0.0, 0.0);
+        }
+        let y = 1.0 - x;
+        let dx = toms708::brcomp(p as f64, q as f64, x as f64, y as f64) as $kind / (x * y);
+
+        // The series of Algorithm AS 63 is evaluated along with its derivatives with respect to
+        // the shape parameters after the flip, which are denoted by P and Q.
+        let flip = p < (p + q) * x;
+        let (p, q, pbase, qbase) = if flip {
+            (q, p, 1.0 - x, x)
+        } else {
+            (p, q, x, y)
+        };
+
+        let mut psq = p + q;
+        let mut ns = (q + qbase * psq) as isize;
+        let mut rx = if ns == 0 { pbase } else { pbase / qbase };
+
+        let (mut term, mut term_p, mut term_q) = (1.0, 0.0, 0.0);
+        let (mut a, mut a_p, mut a_q) = (1.0, 0.0, 0.0);
+        let mut ai = 1.0;
+        let (mut temp, mut temp_p, mut temp_q) = (q - ai, 0.0, 1.0);
 
         loop {
-            term = term * temp * rx / (p + ai);
+            let c = rx / (p + ai);
+            let c_p = -c / (p + ai);
+            term_p = (term_p * temp + term * temp_p) * c + term * temp * c_p;
+            term_q = (term_q * temp + term * temp_q) * c;
+            term = term * temp * c;
 
             a += term;
+            a_p += term_p;
+            a_q += term_q;
 
-            temp = if term < 0.0 { -term } else { term };
-            if temp <= ACU && temp <= ACU * a {
+            if term.abs() <= ACU * a
+                && term_p.abs() <= ACU * a.max(a_p.abs())
+                && term_q.abs() <= ACU * a.max(a_q.abs())
+            {
                 break;
             }
 
             ai += 1.0;
             ns -= 1;
 
-            if 0 < ns {
-                temp = q - ai;
-            } else if ns == 0 {
+            if 0 <= ns {
+                if ns == 0 {
+                    rx = pbase;
+                }
                 temp = q - ai;
-                rx = pbase;
+                temp_p = 0.0;
+                temp_q = 1.0;
             } else {
                 temp = psq;
+                temp_p = 1.0;
+                temp_q = 1.0;
                 psq += 1.0;
             }
         }
 
-        // Remark AS R19 and Algorithm AS 109
-        // http://www.jstor.org/stable/2346887
-        a = a * (p * pbase.ln() + (q - 1.0) * qbase.ln() - ln_beta).exp() / p;
+        let factor = toms708::brcomp(p as f64, q as f64, pbase as f64, qbase as f64) as $kind
+            / (p * qbase);
+        let dp = factor * (a_p + a * (pbase.ln() + digamma_increment!($kind, p, q) - 1.0 / p));
+        let dq = factor * (a_q + a * (qbase.ln() + digamma_increment!($kind, q, p)));
 
         if flip {
-            1.0 - a
+            (value, dx, -dq, -dp)
         } else {
-            a
+            (value, dx, dp, dq)
         }
     }
 
-    fn inv_inc_beta(self, mut p: Self, mut q: Self, ln_beta: Self) -> Self {
+    #[inline]
+    fn inv_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self {
+        self.inv_inc_beta_with_convergence(p, q, ln_beta).0
+    }
+
+    fn inv_inc_beta_with_convergence(
+        self,
+        mut p: Self,
+        mut q: Self,
+        ln_beta: Self,
+    ) -> (Self, Convergence) {
         // Algorithm AS 64
         // http://www.jstor.org/stable/2346798
         //
@@ -188,14 +672,18 @@ macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
         const SAE: i32 = -30;
         const FPU: $kind = 1e-30; // 10^SAE
 
+        const MAX_ITERATIONS: usize = 1000;
+        const TOLERANCE: $kind = 1e3 * <$kind>::EPSILON;
+
         let mut a = self;
         debug_assert!(a >= 0.0 && a <= 1.0 && p > 0.0 && q > 0.0);
 
+        let mut convergence = Convergence { iterations: 0, converged: true };
         if a == 0.0 {
-            return 0.0;
+            return (0.0, convergence);
         }
         if a == 1.0 {
-            return 1.0;
+            return (1.0, convergence);
         }
 
         let mut x;
@@ -209,7 +697,7 @@ macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
             a = 1.0 - a;
         }
 
-        x = (-(a * a).ln()).sqrt();
+        x = (-2.0 * a.ln()).sqrt();
         y = x - (2.30753 + 0.27061 * x) / (1.0 + (0.99229 + 0.04481 * x) * x);
 
         if 1.0 < p && 1.0 < q {
@@ -244,6 +732,8 @@ macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
             x = 0.0001;
         } else if 0.9999 < x {
             x = 0.9999;
+        } else if x.is_nan() {
+            x = 0.5;
         }
 
         // Remark AS R83
@@ -256,11 +746,26 @@ macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
         let mut sq = 1.0;
         let mut prev = 1.0;
 
+        // The root is kept bracketed for the bisection fallback.
+        let (mut lo, mut f_lo) = (0.0, -a);
+        let (mut hi, mut f_hi) = (1.0, 1.0 - a);
+        let mut failed = false;
+
         'outer: loop {
             // Remark AS R19 and Algorithm AS 109
             // http://www.jstor.org/stable/2346887
-            y = x.inc_beta(p, q, ln_beta);
-            y = (y - a) * (ln_beta + (1.0 - p) * x.ln() + (1.0 - q) * (1.0 - x).ln()).exp();
+            y = x.inc_beta(p, q, ln_beta) - a;
+            convergence.iterations += 1;
+            if y < 0.0 && x > lo {
+                (lo, f_lo) = (x, y);
+            } else if y > 0.0 && x < hi {
+                (hi, f_hi) = (x, y);
+            }
+            y *= (ln_beta + (1.0 - p) * x.ln() + (1.0 - q) * (1.0 - x).ln()).exp();
+            if !y.is_finite() || convergence.iterations >= MAX_ITERATIONS {
+                failed = true;
+                break;
+            }
 
             // Remark AS R83
             // http://www.jstor.org/stable/2347779
@@ -305,17 +810,168 @@ macro_rules! implement { ($kind:ident) => { impl Beta for $kind {
             yprev = y;
         }
 
+        // The solution is accepted, after taking one more Newton step, if the step is negligible
+        // relative to it.
+        if !failed {
+            y = x.inc_beta(p, q, ln_beta) - a;
+            convergence.iterations += 1;
+            if y < 0.0 && x > lo {
+                (lo, f_lo) = (x, y);
+            } else if y > 0.0 && x < hi {
+                (hi, f_hi) = (x, y);
+            }
+            let step = y * (ln_beta + (1.0 - p) * x.ln() + (1.0 - q) * (1.0 - x).ln()).exp();
+            if step.abs() <= TOLERANCE * x {
+                x = (x - step).max(lo).min(hi);
+            } else {
+                failed = y != 0.0;
+            }
+        }
+
+        // Otherwise, the bracket is bisected with respect to the binary representation, which
+        // takes at most as many steps as there are bits.
+        if failed {
+            let (mut lo, mut hi) = (lo.to_bits(), hi.to_bits());
+            while lo + 1 < hi && convergence.iterations < MAX_ITERATIONS {
+                let middle = <$kind>::from_bits(lo + (hi - lo) / 2);
+                let f = middle.inc_beta(p, q, ln_beta) - a;
+                convergence.iterations += 1;
+                if f < 0.0 {
+                    lo = middle.to_bits();
+                    f_lo = f;
+                } else if f > 0.0 {
+                    hi = middle.to_bits();
+                    f_hi = f;
+                } else {
+                    lo = middle.to_bits();
+                    hi = lo;
+                }
+            }
+            convergence.converged = lo + 1 >= hi;
+            x = if -f_lo < f_hi {
+                <$kind>::from_bits(lo)
+            } else {
+                <$kind>::from_bits(hi)
+            };
+        }
+
         if flip {
-            1.0 - x
+            (1.0 - x, convergence)
         } else {
-            x
+            (x, convergence)
+        }
+    }
+
+    fn inv_compl_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self {
+        let a = self;
+        debug_assert!(a >= 0.0 && a <= 1.0 && p > 0.0 && q > 0.0);
+
+        if 0.5 < a {
+            (-a).ln_1p().inv_ln_inc_beta(p, q, ln_beta)
+        } else {
+            1.0 - a.ln().inv_ln_inc_beta(q, p, ln_beta)
+        }
+    }
+
+    fn inv_ln_inc_beta(self, p: Self, q: Self, ln_beta: Self) -> Self {
+        const MAX_ITERATIONS: usize = 100;
+
+        let ln_a = self;
+        debug_assert!(ln_a <= 0.0 && p > 0.0 && q > 0.0);
+
+        if ln_a == <$kind>::NEG_INFINITY {
+            return 0.0;
+        }
+        if ln_a == 0.0 {
+            return 1.0;
+        }
+        if -core::$kind::consts::LN_2 < ln_a {
+            return 1.0 - (-ln_a.exp_m1()).ln().inv_ln_inc_beta(q, p, ln_beta);
+        }
+
+        // The starting point is given by inverting the leading term x^p / (p B(p, q)) of the
+        // series, and the iteration converges since ln I is typically concave in ln x.
+        let mut u = ((ln_a + p.ln() + ln_beta) / p).min(-core::$kind::consts::LN_2);
+
+        for _ in 0..MAX_ITERATIONS {
+            let x = u.exp();
+            let ln_y = x.ln_inc_beta(p, q, ln_beta);
+            let ln_slope = p * u + (q - 1.0) * (-x).ln_1p() - ln_beta - ln_y;
+            let mut next = u - (ln_y - ln_a) / ln_slope.exp();
+            if next >= 0.0 || next.is_nan() {
+                next = 0.5 * u;
+            }
+            let done = (next - u).abs() <= <$kind>::EPSILON * u.abs();
+            u = next;
+            if done {
+                break;
+            }
+        }
+
+        u.exp()
+    }
+
+    fn inv_inc_beta_p(self, x: Self, q: Self) -> Self {
+        let a = self;
+        debug_assert!(a >= 0.0 && a <= 1.0 && x >= 0.0 && x <= 1.0 && q > 0.0);
+
+        if a == 0.0 || x == 0.0 {
+            return <$kind>::INFINITY;
+        }
+        if a == 1.0 || x == 1.0 {
+            return 0.0;
+        }
+
+        solve_shape!($kind, |p: $kind| a - x.inc_beta(p, q, p.ln_beta(q)))
+    }
+
+    fn inv_inc_beta_q(self, x: Self, p: Self) -> Self {
+        let a = self;
+        debug_assert!(a >= 0.0 && a <= 1.0 && x >= 0.0 && x <= 1.0 && p > 0.0);
+
+        if a == 0.0 || x == 1.0 {
+            return 0.0;
+        }
+        if a == 1.0 || x == 0.0 {
+            return <$kind>::INFINITY;
         }
+
+        solve_shape!($kind, |q: $kind| x.inc_beta(p, q, p.ln_beta(q)) - a)
     }
 
     fn ln_beta(self, other: Self) -> Self {
         debug_assert!(self > 0.0 && other > 0.0);
         self.ln_gamma().0 + other.ln_gamma().0 - (self + other).ln_gamma().0
     }
+
+    fn ln_multi_beta(alpha: &[Self]) -> Self {
+        debug_assert!(alpha.iter().all(|&a| a > 0.0));
+        if alpha.is_empty() {
+            return <$kind>::NAN;
+        }
+        let mut sum = alpha[0] as f64;
+        let mut value = 0.0;
+        for &a in &alpha[1..] {
+            value += toms708::betaln(sum, a as f64);
+            sum += a as f64;
+        }
+        value as $kind
+    }
+
+    fn ln_multi_beta_grad(alpha: &[Self], gradient: &mut [Self]) -> Self {
+        assert_eq!(alpha.len(), gradient.len());
+        let sum = alpha.iter().sum::<Self>();
+        for (i, &a) in alpha.iter().enumerate() {
+            // The sum of the other parameters is recomputed for the one that dominates.
+            let rest = if 2.0 * a > sum {
+                alpha.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &b)| b).sum()
+            } else {
+                sum - a
+            };
+            gradient[i] = -digamma_increment!($kind, a, rest);
+        }
+        Self::ln_multi_beta(alpha)
+    }
 }}}
 
 implement!(f32);
@@ -324,7 +980,6 @@ implement!(f64);
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
-    use assert;
 
     use super::Beta;
 
@@ -406,6 +1061,198 @@ mod tests {
         assert::close(&y, &a, 1e-14);
     }
 
+    #[test]
+    fn inc_beta_huge() {
+        // References:
+        // mpmath continued fraction with dps = 50
+        const CASES: [(f64, f64, f64, f64); 12] = [
+            (0.4977639376126492, 1e5, 1e5, 2.2749996968316824e-02),
+            (0.5, 1e5, 1e5, 5.0e-01),
+            (0.5033540935810262, 1e5, 1e5, 9.986502016841697e-01),
+            (0.3298906872077766, 1e5, 2e5, 3.081005140900730e-05),
+            (0.3333333333333333, 1e5, 2e5, 5.001716775105407e-01),
+            (0.3341939948647225, 1e5, 2e5, 8.413444100758041e-01),
+            (9.99990000099999e-06, 0.1, 1e4, 8.275479820848597e-01),
+            (1.0486206385669913e-4, 0.1, 1e4, 9.776694302960286e-01),
+            (0.9996671951385857, 1e4, 0.5, 9.876491144409773e-03),
+            (0.999950002499875, 1e4, 0.5, 3.173226062477762e-01),
+            (9.9999990000001e-08, 1e-3, 1e4, 9.936875970732636e-01),
+            (0.21608491224077983, 30.0, 35.0, 6.388209324603672e-06),
+        ];
+        for (x, p, q, expected) in CASES {
+            let actual = x.inc_beta(p, q, p.ln_beta(q));
+            assert::close(actual / expected, 1.0, 1e-13);
+        }
+    }
+
+    #[test]
+    fn ln_inc_beta() {
+        let xs = [0.1, 0.6, 1e-5, 0.01, 0.001, 0.999, 0.9, 0.95];
+        let ps = [2.0, 0.5, 50.0, 200.0, 150.0, 3.0, 1e4, 1e4];
+        let qs = [3.0, 0.5, 2.0, 10.0, 200.0, 400.0, 2e3, 2e3];
+        // References:
+        // mpmath with dps = 50
+        let lower: [f64; 8] = [
+            -2.9507589079112597,
+            -0.57253399028490316,
+            -571.71445741975672,
+            -886.0194894864778,
+            -801.04803291792191,
+            0.0,
+            -7.0477794903858677e-112,
+            0.0,
+        ];
+        let upper: [f64; 8] = [
+            -0.053717282505987857,
+            -0.83032915267501782,
+            -5.0999500000000209e-249,
+            0.0,
+            0.0,
+            -2751.8068412656323,
+            -255.93681781402383,
+            -1102.1171936596526,
+        ];
+        for i in 0..xs.len() {
+            let ln_beta = ps[i].ln_beta(qs[i]);
+            let (x, p, q) = (xs[i], ps[i], qs[i]);
+            assert::close(
+                x.ln_inc_beta(p, q, ln_beta),
+                lower[i],
+                1e-13 * lower[i].abs(),
+            );
+            assert::close(
+                x.ln_compl_inc_beta(p, q, ln_beta),
+                upper[i],
+                1e-13 * upper[i].abs(),
+            );
+        }
+
+        let ln_beta = 2.0.ln_beta(3.0);
+        assert_eq!(0.0.ln_inc_beta(2.0, 3.0, ln_beta), f64::NEG_INFINITY);
+        assert_eq!(0.0.ln_compl_inc_beta(2.0, 3.0, ln_beta), 0.0);
+        assert_eq!(1.0.ln_inc_beta(2.0, 3.0, ln_beta), 0.0);
+        assert_eq!(1.0.ln_compl_inc_beta(2.0, 3.0, ln_beta), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn unreg_inc_beta() {
+        let xs = [0.3, 0.9, 0.3, 0.7, 0.7, 0.2, 0.8, 0.6, 0.4, 0.95];
+        let abs = [
+            (2.5, 1.5),
+            (0.5, 0.5),
+            (-0.5, 2.0),
+            (-1.5, 0.5),
+            (1.5, -0.5),
+            (0.5, -2.0),
+            (0.5, -2.0),
+            (-2.5, -1.5),
+            (300.0, 400.0),
+            (-0.5, 30.0),
+        ];
+        // References:
+        // x**a / a * mpmath.hyp2f1(a, 1 - b, a + 1, x)
+        let ys: [f64; 10] = [
+            0.017464059205992956,
+            2.4980915447965089,
+            -4.7469288317114397,
+            -1.4963512473325194,
+            1.0727372904415085,
+            1.1295572360602553,
+            15.617168460132747,
+            -0.37195955353374317,
+            2.9553815374245114e-210,
+            -19.172369803294983,
+        ];
+        for i in 0..xs.len() {
+            let y = xs[i].unreg_inc_beta(abs[i].0, abs[i].1);
+            assert::close(y, ys[i], 1e-13 * ys[i].abs());
+        }
+        assert!(0.5f64.unreg_inc_beta(-2.0, 1.0).is_nan());
+        assert_eq!(0.0.unreg_inc_beta(2.0, 3.0), 0.0);
+        assert::close(1.0.unreg_inc_beta(2.0, 3.0), 1.0 / 12.0, 1e-15);
+
+        let x = 1.0 - 1e-12;
+        let abs = [
+            (0.5, 0.0),
+            (2.5, 0.0),
+            (-1.5, 0.0),
+            (0.5, -2.0),
+            (-1.5, -2.0),
+            (3.0, -1.0),
+        ];
+        // References:
+        // x**a / a * mpmath.hyp2f1(a, 1 - b, a + 1, x) with dps = 50
+        let ys: [f64; 6] = [
+            29.017337599012749,
+            26.350670932348082,
+            26.350670932344083,
+            5.0002212245469892e+23,
+            5.0002212245669896e+23,
+            1000022122154.2407,
+        ];
+        for i in 0..abs.len() {
+            let y = x.unreg_inc_beta(abs[i].0, abs[i].1);
+            assert::close(y, ys[i], 1e-13 * ys[i].abs());
+        }
+    }
+
+    #[test]
+    fn inc_beta_grad() {
+        let xs = [0.3, 0.7, 0.2, 0.5, 0.9, 0.45, 0.33, 0.501];
+        let ps = [0.5, 2.0, 0.1, 3.0, 0.5, 120.0, 1e3, 1e5];
+        let qs = [5.0, 3.0, 0.2, 3.0, 40.0, 150.0, 2e3, 1e5];
+        // References:
+        // mpmath.betainc and mpmath.diff with dps = 60 and mpmath.quad with dps = 50 for the last
+        // two
+        let values = [
+            0.93473775383109182,
+            0.91629999999999997,
+            0.59253465735547831,
+            0.5,
+            1.0,
+            0.57453515786853439,
+            0.35063267613418342,
+            0.81445325544244325,
+        ];
+        let dxs = [
+            0.53938904437437467,
+            0.75600000000000018,
+            0.34855141858217209,
+            1.875,
+            3.7495285921718158e-39,
+            12.92895498779854,
+            43.206748127603204,
+            239.18729835074406,
+        ];
+        let dps = [
+            -0.17301885179936322,
+            -0.069571251131060503,
+            -2.6920534279154468,
+            -0.17469859027997265,
+            -5.1952194253653074e-41,
+            -0.026570416651955667,
+            -0.0096037626244522971,
+            -0.00059737067597383442,
+        ];
+        let dqs = [
+            0.028187521010860959,
+            0.081872523722080863,
+            1.0462649617370466,
+            0.17469859027997265,
+            2.1670866227919907e-41,
+            0.021490452601506263,
+            0.0047655316196584414,
+            0.00059856661306354523,
+        ];
+        for i in 0..xs.len() {
+            let (value, dx, dp, dq) = xs[i].inc_beta_grad(ps[i], qs[i], ps[i].ln_beta(qs[i]));
+            assert::close(value, values[i], 1e-15);
+            assert::close(dx / dxs[i], 1.0, 1e-12);
+            assert::close(dp / dps[i], 1.0, 1e-12);
+            assert::close(dq / dqs[i], 1.0, 1e-12);
+        }
+    }
+
     #[test]
     fn inv_inc_beta_small() {
         let (p, q) = (0.2, 0.3);
@@ -484,6 +1331,155 @@ mod tests {
         assert::close(&y, &x, 1e-14);
     }
 
+    #[test]
+    fn inv_inc_beta_extreme() {
+        let alphas = [0.5, 1e-5, 0.01, 0.3];
+        let pqs = [(0.01, 1e4), (0.5, 0.5), (0.1, 20.0), (0.1, 1e4)];
+        // References:
+        // mpmath with dps = 50
+        let xs = [
+            4.4657560719752802e-35,
+            2.467401100069404e-10,
+            3.1060835037233455e-22,
+            3.5862473974214045e-10,
+        ];
+        for i in 0..alphas.len() {
+            let (p, q) = pqs[i];
+            let (x, convergence) = alphas[i].inv_inc_beta_with_convergence(p, q, p.ln_beta(q));
+            assert!(convergence.converged);
+            assert::close(x, xs[i], 1e-13 * xs[i]);
+        }
+    }
+
+    #[test]
+    fn inv_compl_inc_beta() {
+        let (p, q) = (3.0, 2.0);
+        let ln_beta = p.ln_beta(q);
+        let alphas = [1e-20, 0.3, 0.9, 0.96875];
+        // References:
+        // mpmath with dps = 50
+        let xs = [
+            0.99999999995917517,
+            0.72761605792489465,
+            0.32046058372181833,
+            0.21009666816829362,
+        ];
+        for i in 0..alphas.len() {
+            let x = alphas[i].inv_compl_inc_beta(p, q, ln_beta);
+            assert::close(x, xs[i], 1e-14 * xs[i]);
+        }
+    }
+
+    #[test]
+    fn inv_ln_inc_beta() {
+        let ln_alphas = [-1000.0, -50.0, -1e-25, -800.0, -2.0];
+        let pqs = [
+            (5.0, 3.0),
+            (0.5, 2.0),
+            (2.0, 3.0),
+            (200.0, 300.0),
+            (0.3, 0.7),
+        ];
+        // References:
+        // mpmath with dps = 50
+        let xs = [
+            7.5276559304472212e-88,
+            1.6533671004537049e-44,
+            0.99999999707598226,
+            0.0034887552211521822,
+            0.0021160999059073169,
+        ];
+        for i in 0..ln_alphas.len() {
+            let (p, q) = pqs[i];
+            let x = ln_alphas[i].inv_ln_inc_beta(p, q, p.ln_beta(q));
+            assert::close(x, xs[i], 1e-13 * xs[i]);
+        }
+    }
+
+    #[test]
+    fn inv_inc_beta_p() {
+        // References:
+        // mpmath.findroot on mpmath.betainc with dps = 50
+        const CASES: [(f64, f64, f64, f64); 6] = [
+            (0.2, 0.5, 5.0, 7.9132206386342598),
+            (0.05, 0.1, 3.0, 2.0257677368131901),
+            (0.95, 0.9, 0.2, 0.016807294118931754),
+            (1e-10, 0.01, 1e3, 36.85614154433265),
+            (0.3, 0.999, 0.5, 537.07841304960579),
+            (1e-300, 0.9999999999999999, 1.0, 6.2219528200781055e18),
+        ];
+        for (a, x, q, p) in CASES {
+            assert::close(a.inv_inc_beta_p(x, q) / p, 1.0, 1e-13);
+        }
+    }
+
+    #[test]
+    fn inv_inc_beta_q() {
+        // References:
+        // mpmath.findroot on mpmath.betainc with dps = 50
+        const CASES: [(f64, f64, f64, f64); 6] = [
+            (0.2, 0.5, 5.0, 2.7930269894965591),
+            (0.05, 0.1, 3.0, 6.8125653519438123),
+            (0.95, 0.9, 0.2, 0.71747049822403838),
+            (1e-10, 0.99, 1e3, 2.5274373844392068e-5),
+            (0.3, 0.001, 0.5, 74.448364760502799),
+            (1e-25, 0.5, 1.0, 1.4426950408889635e-25),
+        ];
+        for (a, x, p, q) in CASES {
+            assert::close(a.inv_inc_beta_q(x, p) / q, 1.0, 1e-13);
+        }
+    }
+
+    #[test]
+    fn ln_multi_beta() {
+        let alphas: [&[f64]; 4] = [
+            &[0.5, 2.0, 3.5],
+            &[1e10, 0.5, 1e-3],
+            &[1e-5, 1e-5],
+            &[100.0, 200.0, 300.0, 0.25],
+        ];
+        // References:
+        // mpmath.loggamma and mpmath.digamma with dps = 50
+        let values = [
+            -3.0141531975102717,
+            -4.0564074875791152,
+            12.206072645365683,
+            -609.91951667573397,
+        ];
+        let gradients: [&[f64]; 4] = [
+            &[
+                -3.669627694453224,
+                -1.2833333333333333,
+                -0.60296102778655729,
+            ],
+            &[
+                -5.0100000001249995e-11,
+                -24.98936095596198,
+                -1023.6014228617508,
+            ],
+            &[-50000.000016448976, -50000.000016448976],
+            &[
+                -1.7963511648189119,
+                -1.1006977343370877,
+                -0.694398135492362,
+                -10.623966550933265,
+            ],
+        ];
+        for i in 0..alphas.len() {
+            let mut gradient = [0.0; 4];
+            let gradient = &mut gradient[..alphas[i].len()];
+            let value = f64::ln_multi_beta_grad(alphas[i], gradient);
+            assert::close(value, values[i], 1e-13 * values[i].abs());
+            assert_eq!(value, f64::ln_multi_beta(alphas[i]));
+            for j in 0..gradient.len() {
+                assert::close(gradient[j], gradients[i][j], 1e-13 * gradients[i][j].abs());
+            }
+        }
+
+        assert!(f64::ln_multi_beta(&[]).is_nan());
+        assert!(f64::ln_multi_beta_grad(&[], &mut []).is_nan());
+    }
+
     #[test]
     fn ln_beta() {
         let x = vec![(0.25, 0.5), (0.5, 0.75), (0.75, 1.0), (1.0, 1.25)];
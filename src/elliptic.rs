@@ -1,4 +1,4 @@
-#[cfg(not(feature = "std"))]
+#[cfg(not(any(feature = "std", test)))]
 use crate::primitive::Primitive;
 
 macro_rules! declare_method {
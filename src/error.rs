@@ -1,10 +1,17 @@
 use crate::primitive::Primitive;
 
+#[cfg(feature = "complex")]
+mod complex;
+
 // The value is found empirically. It must be even; otherwise, it lands on the wrong phase of a
 // 1-ulp oscillation at the deepest tail points.
 const HALLEY_ITERATIONS: usize = 8;
 
 /// Error functions.
+///
+/// The trait is implemented for real numbers and, with the `complex` feature, for complex numbers
+/// of type `Complex` from the `num-complex` crate. In the complex case, the functions are computed
+/// via the Faddeeva function; see `Faddeeva`.
 pub trait Error {
     /// Compute the error function.
     fn error(self) -> Self;
@@ -12,6 +19,54 @@ pub trait Error {
     /// Compute the complementary error function.
     fn compl_error(self) -> Self;
 
+    /// Compute the natural logarithm of the complementary error function.
+    ///
+    /// The function remains accurate where `compl_error` underflows, since large positive
+    /// arguments are handled via ln(exp(x²) erfc(x)) - x², and where it is close to one or two,
+    /// since the logarithm is then computed as that of one plus a small quantity.
+    fn ln_compl_error(self) -> Self;
+
+    /// Compute the scaled complementary error function, exp(x²) erfc(x).
+    ///
+    /// The function is accurate over the whole real line: the complementary error function is
+    /// used directly for moderate arguments, an asymptotic expansion is used for large ones, and
+    /// exp(x²) is evaluated with x² split into an exact and a small part so that the scaling does
+    /// not amplify rounding errors. For negative arguments, the function overflows only when
+    /// 2 exp(x²) does.
+    fn scaled_compl_error(self) -> Self;
+
+    /// Compute the imaginary error function, erfi(x) = -i erf(ix).
+    ///
+    /// The function is computed as exp(x²) times `scaled_imag_error` with the exponential split
+    /// into two factors, so that it overflows only when the result does.
+    fn imag_error(self) -> Self;
+
+    /// Compute the scaled imaginary error function, exp(-x²) erfi(x).
+    ///
+    /// The function equals 2 / √π times Dawson’s function and stays bounded on the real line.
+    fn scaled_imag_error(self) -> Self;
+
+    /// Compute Dawson’s function, F(x) = exp(-x²) ∫₀ˣ exp(t²) dt.
+    ///
+    /// The function is computed via its Maclaurin series for |x| < 0.5, via Rybicki’s method for
+    /// |x| < 10⁸, and as 1 / (2x) beyond.
+    fn dawson(self) -> Self;
+
+    /// Compute the repeated integrals of the complementary error function,
+    /// iⁿ erfc(x) = ∫ₓ^∞ iⁿ⁻¹ erfc(t) dt with i⁰ erfc(x) = erfc(x).
+    ///
+    /// The integrals are computed via the recurrence 2n iⁿ erfc(x) = iⁿ⁻² erfc(x) - 2x iⁿ⁻¹ erfc(x)
+    /// applied in the direction in which it is stable.
+    fn repeated_compl_error(self, n: usize) -> Self;
+
+    /// Compute the inverse of the complementary error function.
+    ///
+    /// The function is accurate in the relative sense for arguments down to the smallest
+    /// subnormal number. Arguments close to zero are handled by the tail iteration used for
+    /// `inv_error` without forming 1 - y, and those below the normal range by Newton’s method
+    /// applied to the logarithm of `scaled_compl_error`.
+    fn inv_compl_error(self) -> Self;
+
     /// Compute the inverse of the error function.
     ///
     /// The implementation is based on a [C implementation][1] by Alijah Ahmed.
@@ -20,8 +75,70 @@ pub trait Error {
     fn inv_error(self) -> Self;
 }
 
+// Evaluate the central approximation of the inverse of the error function divided by its
+// argument, where w = -ln(1 - x²).
+macro_rules! central {
+    ($w:expr) => {{
+        let w = $w - 2.5;
+        let mut p = 2.81022636e-08;
+        p = 3.43273939e-07 + p * w;
+        p = -3.5233877e-06 + p * w;
+        p = -4.39150654e-06 + p * w;
+        p = 0.00021858087 + p * w;
+        p = -0.00125372503 + p * w;
+        p = -0.00417768164 + p * w;
+        p = 0.246640727 + p * w;
+        p = 1.50140941 + p * w;
+        p
+    }};
+}
+
+// Evaluate the tail approximation of the inverse of the error function divided by its argument,
+// where w = -ln(1 - x²).
+macro_rules! tail {
+    ($w:expr) => {{
+        let w = $w.sqrt() - 3.0;
+        let mut p = -0.000200214257;
+        p = 0.000100950558 + p * w;
+        p = 0.00134934322 + p * w;
+        p = -0.00367342844 + p * w;
+        p = 0.00573950773 + p * w;
+        p = -0.0076224613 + p * w;
+        p = 0.00943887047 + p * w;
+        p = 1.00167406 + p * w;
+        p = 2.83297682 + p * w;
+        p
+    }};
+}
+
+// Refine x such that erf(x) = 1 - lower = upper - 1 by Halley’s method, where lower and upper are
+// given separately in order to retain the accuracy of complementary values in the tails.
+macro_rules! halley {
+    ($kind:ty, $x:expr, $lower:expr, $upper:expr) => {{
+        const SQRT_PI: $kind = 1.772453850905515881919427556567825376987457275391;
+
+        let (lower, upper): ($kind, $kind) = ($lower, $upper);
+        let mut res_ra: $kind = $x;
+        for _ in 0..HALLEY_ITERATIONS {
+            let fx = if res_ra >= 0.0 {
+                lower - res_ra.compl_error()
+            } else {
+                (-res_ra).compl_error() - upper
+            };
+            let df = 2.0 / SQRT_PI * (-(res_ra * res_ra)).exp();
+            let d2f = -2.0 * res_ra * df;
+            let next = res_ra - (2.0 * fx * df) / ((2.0 * df * df) - (fx * d2f));
+            if next == res_ra {
+                break;
+            }
+            res_ra = next;
+        }
+        res_ra
+    }};
+}
+
 macro_rules! implement {
-    ($kind:ty) => {
+    ($kind:ident) => {
         impl Error for $kind {
             #[inline]
             fn error(self) -> Self {
@@ -33,23 +150,223 @@ macro_rules! implement {
                 Primitive::erfc(self)
             }
 
+            fn ln_compl_error(self) -> Self {
+                use core::$kind::consts::LN_2;
+
+                let x = self;
+                if x <= -0.5 {
+                    LN_2 + (-0.5 * (-x).compl_error()).ln_1p()
+                } else if x < 0.5 {
+                    (-x.error()).ln_1p()
+                } else {
+                    x.scaled_compl_error().ln() - x * x
+                }
+            }
+
+            fn scaled_compl_error(self) -> Self {
+                const FRAC_1_SQRT_PI: $kind = 0.564189583547756286948079451560772586;
+
+                let x = self;
+                if x < 8.0 {
+                    // The value of x is split as h + l where h has few enough bits for h² to be
+                    // exact, so that exp(x²) = exp(h²) exp(l (2h + l)).
+                    let h = (x * 64.0).round() / 64.0;
+                    let l = x - h;
+                    let exp_square = (h * h).exp() * (l * (2.0 * h + l)).exp();
+                    if x < 0.0 {
+                        2.0 * exp_square - (-x).scaled_compl_error()
+                    } else {
+                        exp_square * x.compl_error()
+                    }
+                } else {
+                    let z = 0.5 / (x * x);
+                    let mut sum = 1.0;
+                    let mut term = 1.0;
+                    for n in 1..50 {
+                        term *= -((2 * n - 1) as $kind) * z;
+                        sum += term;
+                        if term.abs() <= <$kind>::EPSILON * sum {
+                            break;
+                        }
+                    }
+                    FRAC_1_SQRT_PI * sum / x
+                }
+            }
+
+            fn imag_error(self) -> Self {
+                let x = self;
+                let h = (x * 64.0).round() / 64.0;
+                let l = x - h;
+                // The factor exp(h²) is applied in two halves in order to postpone overflow.
+                let exp_half_square = (0.5 * h * h).exp();
+                exp_half_square
+                    * (x.scaled_imag_error() * (l * (2.0 * h + l)).exp())
+                    * exp_half_square
+            }
+
+            #[inline]
+            fn scaled_imag_error(self) -> Self {
+                use core::$kind::consts::FRAC_2_SQRT_PI;
+
+                FRAC_2_SQRT_PI * self.dawson()
+            }
+
+            fn dawson(self) -> Self {
+                const FRAC_1_SQRT_PI: f64 = 0.564189583547756286948079451561;
+
+                let x = self;
+                if x.abs() < 0.5 {
+                    // F(x) = Σ (-1)ⁿ 2ⁿ x²ⁿ⁺¹ / (2n + 1)!!
+                    let square = x * x;
+                    let mut term = x;
+                    let mut sum = x;
+                    for n in 1..20 {
+                        term *= -2.0 * square / (2 * n + 1) as $kind;
+                        sum += term;
+                        if term.abs() <= <$kind>::EPSILON * sum.abs() {
+                            break;
+                        }
+                    }
+                    sum
+                } else if x.abs() < 1e8 {
+                    // Rybicki’s method: F(x) ≈ Σ exp(-(x - x₀ - nh)²) / (√π (n + n₀)) over odd n,
+                    // where x₀ = n₀h is the even multiple of h nearest to x.
+                    const H: f64 = 0.2;
+                    let y = (x as f64).abs();
+                    let n0 = 2.0 * (y / (2.0 * H)).round();
+                    let y = y - n0 * H;
+                    let mut sum = 0.0;
+                    for n in (1..36).step_by(2).rev() {
+                        let n = n as f64;
+                        sum += (-(y - n * H) * (y - n * H)).exp() / (n0 + n)
+                            + (-(y + n * H) * (y + n * H)).exp() / (n0 - n);
+                    }
+                    let value = (FRAC_1_SQRT_PI * sum) as $kind;
+                    if x < 0.0 {
+                        -value
+                    } else {
+                        value
+                    }
+                } else {
+                    0.5 / x
+                }
+            }
+
+            fn repeated_compl_error(self, n: usize) -> Self {
+                use core::$kind::consts::FRAC_2_SQRT_PI;
+
+                let x = self;
+                if n == 0 {
+                    return x.compl_error();
+                }
+                // The recurrence is applied forward for x < 0, where it is stable. For x ≥ 1, the
+                // ratios of consecutive integrals are computed via the recurrence applied backward
+                // from an order chosen for double precision, which grows as
+                // (√(2n) + 40 / x)² / 2. For 0 ≤ x < 1, the integrals are computed at 1 = x + δ in
+                // the same way and then continued to x via the Taylor expansion
+                // iⁿ erfc(x) = Σ δᵏ / k! iⁿ⁻ᵏ erfc(x + δ), whose first n + 1 terms are positive.
+                // The result remains accurate for n up to several hundred, beyond which it
+                // underflows.
+                if x < 0.0 {
+                    let mut previous = FRAC_2_SQRT_PI * (-x * x).exp();
+                    let mut current = x.compl_error();
+                    for k in 1..=n {
+                        (previous, current) =
+                            (current, (previous - 2.0 * x * current) / (2 * k) as $kind);
+                    }
+                    return current;
+                }
+                let delta = if x < 1.0 { 1.0 - x } else { 0.0 };
+                let x = x + delta;
+                // The ratios rₖ = iᵏ erfc(x) / iᵏ⁻¹ erfc(x) satisfy
+                // rₖ = 1 / (2 (x + (k + 1) rₖ₊₁)). Along with their product, the terms of the Taylor
+                // expansion are accumulated relative to iⁿ erfc(x), starting from k = 0.
+                let order = (2.0 * n as $kind + 2.0).sqrt() + 40.0 / x;
+                let order = (0.5 * order * order) as usize + 1;
+                let mut ratio: $kind = 0.0;
+                let mut product: $kind = 1.0;
+                let mut term: $kind = 1.0;
+                let mut sum: $kind = 1.0;
+                for k in (1..order).rev() {
+                    ratio = 0.5 / (x + (k + 1) as $kind * ratio);
+                    if k <= n {
+                        product *= ratio;
+                        if delta > 0.0 {
+                            term *= delta / ((n - k + 1) as $kind * ratio);
+                            sum += term;
+                        }
+                    }
+                }
+                if delta > 0.0 {
+                    // The terms with k > n involve i⁻ʲ erfc(x), which are the derivatives of
+                    // 2 / √π exp(-x²) and follow from the recurrence applied downward, starting
+                    // from i⁻¹ erfc(x) / i⁰ erfc(x) = 2 (x + r₁).
+                    let mut previous = term;
+                    let mut current = term * delta * 2.0 * (x + ratio) / (n + 1) as $kind;
+                    sum += current;
+                    for j in 1..100 {
+                        let next = delta / (n + j + 1) as $kind
+                            * (2.0 * x * current
+                                + 2.0 * (1.0 - j as $kind) * delta / (n + j) as $kind * previous);
+                        sum += next;
+                        (previous, current) = (current, next);
+                        if current.abs() <= <$kind>::EPSILON * sum
+                            && previous.abs() <= <$kind>::EPSILON * sum
+                        {
+                            break;
+                        }
+                    }
+                }
+                x.compl_error() * sum * product
+            }
+
+            fn inv_compl_error(self) -> Self {
+                const FRAC_SQRT_PI_2: $kind = 0.886226925452758013649083741671;
+
+                let y = self;
+                debug_assert!((0.0..=2.0).contains(&y));
+
+                if y > 1.0 {
+                    return -(2.0 - y).inv_compl_error();
+                }
+                if y >= 0.5 {
+                    return (1.0 - y).inv_error();
+                }
+                if y == 0.0 {
+                    return <$kind>::INFINITY;
+                }
+
+                let w: $kind = -(y * (2.0 - y)).ln();
+                if w < 5.0 {
+                    return halley!($kind, central!(w) * (1.0 - y), y, 2.0 - y);
+                }
+                if w <= 36.0 {
+                    return halley!($kind, tail!(w) * (1.0 - y), y, 2.0 - y);
+                }
+
+                // Beyond the range of the tail approximation, which covers arguments down to
+                // machine epsilon, Newton’s method is applied to ln erfc(x) - ln y, which is
+                // computed as ln(exp(x²) erfc(x)) - x² - ln y in order to avoid underflow.
+                let ln_y = y.ln();
+                let mut x = (-ln_y).sqrt();
+                for _ in 0..HALLEY_ITERATIONS {
+                    let scaled = x.scaled_compl_error();
+                    let step = (scaled.ln() - (x * x + ln_y)) * FRAC_SQRT_PI_2 * scaled;
+                    x += step;
+                    if step.abs() <= <$kind>::EPSILON * x {
+                        break;
+                    }
+                }
+                x
+            }
+
             fn inv_error(self) -> Self {
                 const SQRT_PI: $kind = 1.772453850905515881919427556567825376987457275391;
 
-                let mut w: $kind = -((1.0 - self) * (1.0 + self)).ln();
-                let mut p: $kind;
+                let w: $kind = -((1.0 - self) * (1.0 + self)).ln();
 
                 if w < 5.0 {
-                    w -= 2.5;
-                    p = 2.81022636e-08;
-                    p = 3.43273939e-07 + p * w;
-                    p = -3.5233877e-06 + p * w;
-                    p = -4.39150654e-06 + p * w;
-                    p = 0.00021858087 + p * w;
-                    p = -0.00125372503 + p * w;
-                    p = -0.00417768164 + p * w;
-                    p = 0.246640727 + p * w;
-                    p = 1.50140941 + p * w;
+                    let p = central!(w);
 
                     let res_ra = p * self;
                     let fx: Self = res_ra.error() - self;
@@ -62,33 +379,8 @@ macro_rules! implement {
                 } else if self == -1.0 {
                     return <$kind>::NEG_INFINITY;
                 } else {
-                    w = w.sqrt() - 3.0;
-                    p = -0.000200214257;
-                    p = 0.000100950558 + p * w;
-                    p = 0.00134934322 + p * w;
-                    p = -0.00367342844 + p * w;
-                    p = 0.00573950773 + p * w;
-                    p = -0.0076224613 + p * w;
-                    p = 0.00943887047 + p * w;
-                    p = 1.00167406 + p * w;
-                    p = 2.83297682 + p * w;
-
-                    let mut res_ra = p * self;
-                    for _ in 0..HALLEY_ITERATIONS {
-                        let fx = if res_ra >= 0.0 {
-                            (1.0 - self) - res_ra.compl_error()
-                        } else {
-                            (-res_ra).compl_error() - (1.0 + self)
-                        };
-                        let df = 2.0 / SQRT_PI * (-(res_ra * res_ra)).exp();
-                        let d2f = -2.0 * res_ra * df;
-                        let next = res_ra - (2.0 * fx * df) / ((2.0 * df * df) - (fx * d2f));
-                        if next == res_ra {
-                            break;
-                        }
-                        res_ra = next;
-                    }
-                    res_ra
+                    let p = tail!(w);
+                    halley!($kind, p * self, 1.0 - self, 1.0 + self)
                 }
             }
         }
@@ -104,6 +396,187 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn ln_compl_error() {
+        let x: [f64; 12] = [
+            -30.0, -5.0, -0.3, -1e-10, 0.0, 1e-10, 0.3, 0.7, 5.0, 30.0, 1e3, 1e10,
+        ];
+        // References:
+        // mpmath.log(mpmath.erfc(x)) with dps = 60
+        let y: [f64; 12] = [
+            core::f64::consts::LN_2,
+            0.69314718055917658,
+            0.28414589714140826,
+            1.1283791670318506e-10,
+            0.0,
+            -1.1283791671591746e-10,
+            -0.39843005144008529,
+            -1.1325865135143413,
+            -27.200889545537434,
+            -903.97411711064388,
+            -1000007.4801207219,
+            -1.0e+20,
+        ];
+        for i in 0..x.len() {
+            assert::close(x[i].ln_compl_error(), y[i], 4e-16 * y[i].abs());
+        }
+    }
+
+    #[test]
+    fn scaled_compl_error() {
+        let x: [f64; 12] = [
+            -26.0, -5.0, -1.0, -0.001, 0.0, 0.5, 3.0, 7.9, 8.1, 30.0, 1e3, 1e10,
+        ];
+        // References:
+        // mpmath.erfc(x) * mpmath.exp(x * x) with dps = 40
+        let y: [f64; 12] = [
+            7.6577249314905684e+293,
+            144009798674.66104,
+            5.0089800807622835,
+            1.0011293799198486,
+            1.0,
+            0.61569034419292587,
+            0.17900115118138995,
+            0.070857477367397134,
+            0.069133920177343149,
+            0.018795888861416751,
+            0.00056418930145338765,
+            5.6418958354775629e-11,
+        ];
+        for i in 0..x.len() {
+            assert::close(x[i].scaled_compl_error(), y[i], 4e-16 * y[i]);
+        }
+        assert_eq!((-27.0f64).scaled_compl_error(), f64::INFINITY);
+        assert_eq!(f64::INFINITY.scaled_compl_error(), 0.0);
+    }
+
+    #[test]
+    fn imag_error() {
+        let x: [f64; 10] = [-26.0, -5.0, -0.5, 1e-3, 0.0, 0.3, 1.0, 3.0, 10.0, 26.5];
+        // References:
+        // mpmath.erfi(x) with dps = 40
+        let y: [f64; 10] = [
+            -8.3146371647309877e+291,
+            -8298273880.6768035,
+            -0.61495209469651098,
+            0.0011283795432220145,
+            0.0,
+            0.34894933875893617,
+            1.6504257587975429,
+            1629.9946226015657,
+            1.5243074227086697e+42,
+            2.0501652832248793e+303,
+        ];
+        for i in 0..x.len() {
+            assert::close(x[i].imag_error(), y[i], 1e-15 * y[i].abs());
+        }
+        assert_eq!(27.0f64.imag_error(), f64::INFINITY);
+    }
+
+    #[test]
+    fn dawson() {
+        let x: [f64; 15] = [
+            -30.0, -3.0, -0.7, -1e-3, 0.0, 0.2, 0.49, 0.51, 1.0, 5.0, 7.9, 8.1, 20.0, 1e5, 1e9,
+        ];
+        // References:
+        // mpmath.sqrt(mpmath.pi) / 2 * mpmath.exp(-x * x) * mpmath.erfi(x) with dps = 40
+        let y: [f64; 15] = [
+            -0.016675941401059176,
+            -0.17827103061055829,
+            -0.51050405755923177,
+            -0.00099999933333360002,
+            0.0,
+            0.19475103336802806,
+            0.41860967574960343,
+            0.43012065546953283,
+            0.53807950691276842,
+            0.10213407442427684,
+            0.0638109032198449,
+            0.062210002366826793,
+            0.025031367926403672,
+            5.00000000025e-6,
+            5.0e-10,
+        ];
+        for i in 0..x.len() {
+            assert::close(x[i].dawson(), y[i], 1e-15 * y[i].abs());
+        }
+        assert::close(0.3f64.scaled_imag_error(), 0.31891568277156586, 1e-15);
+    }
+
+    #[test]
+    fn repeated_compl_error() {
+        let x: [(usize, f64); 15] = [
+            (0, 0.5),
+            (1, -3.0),
+            (5, -0.5),
+            (2, 0.0),
+            (1, 0.3),
+            (10, 0.5),
+            (30, 0.1),
+            (100, 0.9),
+            (3, 1.0),
+            (7, 2.5),
+            (20, 10.0),
+            (200, 0.7),
+            (250, 0.01),
+            (4, 20.0),
+            (300, 0.0),
+        ];
+        // References:
+        // forward recurrence of mpmath.erfc(x) with sufficient dps
+        let y: [f64; 15] = [
+            0.4795001221869535,
+            6.000003355034978,
+            0.04374437542410533,
+            0.25,
+            0.31421848264721974,
+            7.200063962638894e-7,
+            3.244773518985694e-22,
+            4.9246760949922176e-101,
+            3.6432466324803126e-3,
+            8.878424717845458e-10,
+            7.038616071707603e-72,
+            4.251040952311567e-225,
+            2.346902938119769e-285,
+            2.0714981297361744e-182,
+            0.0,
+        ];
+        for i in 0..x.len() {
+            let (n, x) = x[i];
+            assert::close(x.repeated_compl_error(n), y[i], 1e-14 * y[i]);
+        }
+    }
+
+    #[test]
+    fn inv_compl_error() {
+        let y: [f64; 13] = [
+            5e-324, 1e-310, 1e-300, 1e-100, 1e-20, 1e-5, 0.01, 0.1, 0.3, 0.5, 0.9, 1.5, 1.99,
+        ];
+        // References:
+        // mpmath.erfinv(1 - y) and mpmath.findroot with dps = 80
+        let x: [f64; 13] = [
+            27.213293210812949,
+            26.644806559364765,
+            26.209469960516124,
+            15.065574702592646,
+            6.6015806223551426,
+            3.123413274340875,
+            1.8213863677184497,
+            1.1630871536766741,
+            0.73286907795921687,
+            0.47693627620446987,
+            0.088855990494257667,
+            -0.47693627620446987,
+            -1.8213863677184495,
+        ];
+        for i in 0..y.len() {
+            assert::close(y[i].inv_compl_error(), x[i], 1e-15 * x[i].abs());
+        }
+        assert_eq!(0.0f64.inv_compl_error(), f64::INFINITY);
+        assert_eq!(1.0f64.inv_compl_error(), 0.0);
+        assert_eq!(2.0f64.inv_compl_error(), f64::NEG_INFINITY);
+    }
+
     #[test]
     fn inv_error_negative() {
         assert::close(-0.99.inv_error(), -1.8213863677184492, 1e-12);
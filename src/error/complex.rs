@@ -0,0 +1,477 @@
+use core::f64::consts::FRAC_2_SQRT_PI;
+
+use num_complex::Complex;
+
+use crate::faddeeva::{exp_neg_square, Faddeeva};
+#[allow(unused_imports)]
+use crate::primitive::Primitive;
+use crate::Error;
+
+const FRAC_SQRT_PI_2: f64 = 0.886226925452758013649083741671;
+const SQRT_PI: f64 = 1.772453850905515881919427556567825376987457275391;
+const NEWTON_ITERATIONS: usize = 100;
+
+impl Error for Complex<f64> {
+    fn error(self) -> Self {
+        let z = self;
+        if z.re < 0.0 {
+            return -(-z).error();
+        }
+        if z.norm_sqr() < 0.25 {
+            // erf(z) = 2 / √π Σ (-1)ⁿ z²ⁿ⁺¹ / (n! (2n + 1))
+            let square = z * z;
+            let mut term = z;
+            let mut sum = z;
+            for n in 1..30 {
+                term = -term * square / n as f64;
+                let next = term / (2 * n + 1) as f64;
+                sum += next;
+                if next.norm() <= f64::EPSILON * sum.norm() {
+                    break;
+                }
+            }
+            return FRAC_2_SQRT_PI * sum;
+        }
+        1.0 - z.compl_error()
+    }
+
+    fn compl_error(self) -> Self {
+        let z = self;
+        if z.re < 0.0 {
+            2.0 - exp_neg_square(z) * Complex::new(z.im, -z.re).faddeeva()
+        } else {
+            exp_neg_square(z) * z.scaled_compl_error()
+        }
+    }
+
+    fn ln_compl_error(self) -> Self {
+        let z = self;
+        if z.re < 0.0 {
+            // erfc(z) = 2 - erfc(-z), and the logarithm of the latter is used when its exponential
+            // would overflow.
+            let ln = (-z).ln_compl_error();
+            let value = if ln.re < 700.0 {
+                (2.0 - ln.exp()).ln()
+            } else {
+                ln + (2.0 * (-ln).exp() - 1.0).ln()
+            };
+            principal(value)
+        } else {
+            principal(z.scaled_compl_error().ln() - z * z)
+        }
+    }
+
+    #[inline]
+    fn scaled_compl_error(self) -> Self {
+        Complex::new(-self.im, self.re).faddeeva()
+    }
+
+    #[inline]
+    fn imag_error(self) -> Self {
+        let z = Complex::new(-self.im, self.re).error();
+        Complex::new(z.im, -z.re)
+    }
+
+    #[inline]
+    fn scaled_imag_error(self) -> Self {
+        FRAC_2_SQRT_PI * self.dawson()
+    }
+
+    fn dawson(self) -> Self {
+        let z = self;
+        if z.norm_sqr() < 0.25 {
+            // F(z) = Σ (-1)ⁿ 2ⁿ z²ⁿ⁺¹ / (2n + 1)!!
+            let square = z * z;
+            let mut term = z;
+            let mut sum = z;
+            for n in 1..30 {
+                term = -2.0 * term * square / (2 * n + 1) as f64;
+                sum += term;
+                if term.norm() <= f64::EPSILON * sum.norm() {
+                    break;
+                }
+            }
+            return sum;
+        }
+        // F(z) = i √π / 2 (exp(-z²) - w(z))
+        let v = exp_neg_square(z) - z.faddeeva();
+        Complex::new(-v.im, v.re) * FRAC_SQRT_PI_2
+    }
+
+    fn repeated_compl_error(self, n: usize) -> Self {
+        let z = self;
+        if n == 0 {
+            return z.compl_error();
+        }
+        if z.re < 0.0 {
+            // The forward recurrence is unstable close to the imaginary axis, and the reflection
+            // iⁿ erfc(z) = pₙ(z) - (-1)ⁿ iⁿ erfc(-z) is used instead, where the polynomial pₙ
+            // satisfies the same recurrence with p₋₁ = 0 and p₀ = 2.
+            let mut previous = Complex::new(0.0, 0.0);
+            let mut current = Complex::new(2.0, 0.0);
+            for k in 1..=n {
+                (previous, current) = (current, (previous - 2.0 * z * current) / (2 * k) as f64);
+            }
+            let value = (-z).repeated_compl_error(n);
+            return if n & 1 == 0 {
+                current - value
+            } else {
+                current + value
+            };
+        }
+        // The same scheme as for real arguments is used with the real shift δ taken so that
+        // Re z + δ = 1. The accuracy degrades close to the imaginary axis as |Im z| approaches
+        // √(4n + 64), beyond which the backward recurrence converges quickly and is applied
+        // without a shift.
+        let (delta, order) = if z.norm_sqr() >= (4 * n + 64) as f64 {
+            (0.0, n + 40)
+        } else {
+            let delta = if z.re < 1.0 { 1.0 - z.re } else { 0.0 };
+            let order = (2.0 * n as f64 + 2.0).sqrt() + 40.0 / (z.re + delta);
+            (delta, (0.5 * order * order) as usize + 1)
+        };
+        let z = z + delta;
+        let mut ratio = Complex::new(0.0, 0.0);
+        let mut product = Complex::new(1.0, 0.0);
+        let mut term = Complex::new(1.0, 0.0);
+        let mut sum = Complex::new(1.0, 0.0);
+        for k in (1..order).rev() {
+            ratio = 0.5 / (z + (k + 1) as f64 * ratio);
+            if k <= n {
+                product *= ratio;
+                if delta > 0.0 {
+                    term *= delta / ((n - k + 1) as f64 * ratio);
+                    sum += term;
+                }
+            }
+        }
+        if delta > 0.0 {
+            let mut previous = term;
+            let mut current = term * delta * 2.0 * (z + ratio) / (n + 1) as f64;
+            sum += current;
+            for j in 1..100 {
+                let next = delta / (n + j + 1) as f64
+                    * (2.0 * z * current
+                        + 2.0 * (1.0 - j as f64) * delta / (n + j) as f64 * previous);
+                sum += next;
+                (previous, current) = (current, next);
+                if current.norm() <= f64::EPSILON * sum.norm()
+                    && previous.norm() <= f64::EPSILON * sum.norm()
+                {
+                    break;
+                }
+            }
+        }
+        z.compl_error() * sum * product
+    }
+
+    fn inv_compl_error(self) -> Self {
+        let y = self;
+        if y.im == 0.0 && (0.0..=2.0).contains(&y.re) {
+            return Complex::new(y.re.inv_compl_error(), 0.0);
+        }
+        if y.re > 1.0 {
+            return -(2.0 - y).inv_compl_error();
+        }
+
+        // Newton’s method is applied to ln erfc(z) - ln y, which remains well scaled for
+        // arguments close to zero.
+        let ln_y = y.ln();
+        let mut z = if (1.0 - y).norm() < 0.5 {
+            FRAC_SQRT_PI_2 * (1.0 - y)
+        } else {
+            start(ln_y)
+        };
+        for _ in 0..NEWTON_ITERATIONS {
+            let step =
+                principal(z.ln_compl_error() - ln_y) * FRAC_SQRT_PI_2 * z.scaled_compl_error();
+            z += step;
+            if step.norm() <= f64::EPSILON * z.norm() {
+                break;
+            }
+        }
+        z
+    }
+
+    fn inv_error(self) -> Self {
+        let y = self;
+        if y.im == 0.0 && (-1.0..=1.0).contains(&y.re) {
+            return Complex::new(y.re.inv_error(), 0.0);
+        }
+        if y.re < 0.0 {
+            return -(-y).inv_error();
+        }
+        if (1.0 - y).norm() < 0.5 {
+            return (1.0 - y).inv_compl_error();
+        }
+
+        let mut z = if y.norm() < 0.5 {
+            FRAC_SQRT_PI_2 * y
+        } else {
+            start((1.0 - y).ln())
+        };
+        for _ in 0..NEWTON_ITERATIONS {
+            let step = (z.error() - y) * FRAC_SQRT_PI_2 * (z * z).exp();
+            z -= step;
+            if step.norm() <= f64::EPSILON * z.norm() {
+                break;
+            }
+        }
+        z
+    }
+}
+
+impl Error for Complex<f32> {
+    #[inline]
+    fn error(self) -> Self {
+        narrow(widen(self).error())
+    }
+
+    #[inline]
+    fn compl_error(self) -> Self {
+        narrow(widen(self).compl_error())
+    }
+
+    #[inline]
+    fn ln_compl_error(self) -> Self {
+        narrow(widen(self).ln_compl_error())
+    }
+
+    #[inline]
+    fn scaled_compl_error(self) -> Self {
+        narrow(widen(self).scaled_compl_error())
+    }
+
+    #[inline]
+    fn imag_error(self) -> Self {
+        narrow(widen(self).imag_error())
+    }
+
+    #[inline]
+    fn scaled_imag_error(self) -> Self {
+        narrow(widen(self).scaled_imag_error())
+    }
+
+    #[inline]
+    fn dawson(self) -> Self {
+        narrow(widen(self).dawson())
+    }
+
+    #[inline]
+    fn repeated_compl_error(self, n: usize) -> Self {
+        narrow(widen(self).repeated_compl_error(n))
+    }
+
+    #[inline]
+    fn inv_compl_error(self) -> Self {
+        narrow(widen(self).inv_compl_error())
+    }
+
+    #[inline]
+    fn inv_error(self) -> Self {
+        narrow(widen(self).inv_error())
+    }
+}
+
+// Reduce the imaginary part of a logarithm to (-π, π].
+fn principal(z: Complex<f64>) -> Complex<f64> {
+    use core::f64::consts::PI;
+
+    let mut im = z.im - 2.0 * PI * (z.im / (2.0 * PI)).round();
+    if im <= -PI {
+        im += 2.0 * PI;
+    }
+    Complex::new(z.re, im)
+}
+
+// Compute a starting point for solving erfc(z) = y given ln y, using the leading term of the
+// asymptotic expansion erfc(z) ≈ exp(-z²) / (z √π) in the right half-plane.
+fn start(ln_y: Complex<f64>) -> Complex<f64> {
+    let z = (-ln_y).sqrt();
+    let next = (-ln_y - (SQRT_PI * z).ln()).sqrt();
+    if next.re > 0.0 && next.re.is_finite() && next.im.is_finite() {
+        next
+    } else {
+        z
+    }
+}
+
+#[inline]
+fn widen(z: Complex<f32>) -> Complex<f64> {
+    Complex::new(z.re as f64, z.im as f64)
+}
+
+#[inline]
+fn narrow(z: Complex<f64>) -> Complex<f32> {
+    Complex::new(z.re as f32, z.im as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use crate::Error;
+
+    const Z: [(f64, f64); 10] = [
+        (0.1, 0.2),
+        (0.3, -0.4),
+        (1.0, 1.0),
+        (-2.0, 0.5),
+        (0.5, 3.0),
+        (3.0, -3.0),
+        (-0.2, -5.0),
+        (6.0, 0.5),
+        (-4.0, -0.1),
+        (30.0, -20.0),
+    ];
+
+    fn check<F: Fn(Complex<f64>) -> Complex<f64>>(f: F, y: &[(f64, f64)]) {
+        for (z, y) in Z.iter().zip(y.iter()) {
+            let z = f(Complex::new(z.0, z.1));
+            let y = Complex::new(y.0, y.1);
+            assert!((z - y).norm() <= 1e-13 * y.norm(), "{:?} {:?}", z, y);
+        }
+    }
+
+    #[test]
+    fn error() {
+        // References: mpmath erf(z)
+        let y: [(f64, f64); 10] = [
+            (0.1170214863039043, 0.22638445718145092),
+            (0.38204323258301792, -0.43125203623196416),
+            (1.3161512816979476, 0.19045346923783469),
+            (-1.0035022433130363, 4.7409030312943361e-3),
+            (404.81268348510669, -1.1726091303384733e+3),
+            (0.86782649757545114, 0.012152181790312257),
+            (-7.3751761893113575e+9, 3.0095740737179929e+9),
+            (0.99999999999999997, -5.5310394052704538e-18),
+            (-0.99999998942056539, -1.1421049438186166e-8),
+            (1.0, 2.4450429803398673e-45),
+        ];
+        check(Error::error, &y);
+    }
+
+    #[test]
+    fn compl_error() {
+        // References: mpmath erfc(z)
+        let y: [(f64, f64); 10] = [
+            (0.8829785136960957, -0.22638445718145092),
+            (0.61795676741698208, 0.43125203623196416),
+            (-0.31615128169794764, -0.19045346923783469),
+            (2.0035022433130363, -4.7409030312943361e-3),
+            (-403.81268348510669, 1.1726091303384733e+3),
+            (0.13217350242454886, -0.012152181790312257),
+            (7.3751761903113575e+9, -3.0095740737179929e+9),
+            (2.6982467499622581e-17, 5.5310394052704538e-18),
+            (1.9999999894205654, 1.1421049438186166e-8),
+            (9.7861723541874951e-220, 5.3367369965247591e-220),
+        ];
+        check(Error::compl_error, &y);
+    }
+
+    #[test]
+    fn ln_compl_error() {
+        // References: mpmath log(erfc(z))
+        let y: [(f64, f64); 10] = [
+            (-0.092622325575909547, -0.25098112515143204),
+            (-0.28294997146390061, 0.60929342197947442),
+            (-0.99672676891101982, -2.5994011783616758),
+            (0.6948995704890014, -2.3663034061428595e-3),
+            (7.1230216995616922, 1.9024477519004687),
+            (-2.0194309850395863, -0.091683376021016018),
+            (22.798397922511875, -0.38744222080820208),
+            (-38.130764074609939, 0.20218556416683401),
+            (0.69314717527022801, 5.7105247493001445e-9),
+            (-504.1575729034012, 0.49925416551913117),
+        ];
+        check(Error::ln_compl_error, &y);
+    }
+
+    #[test]
+    fn imag_error() {
+        // References: mpmath erfi(z)
+        let y: [(f64, f64); 10] = [
+            (0.10874686167958863, 0.22488144533923799),
+            (0.29443980776931706, -0.46443721297956142),
+            (0.19045346923783469, 1.3161512816979476),
+            (1.0429925008314203, 13.839985667741279),
+            (-2.6284897222588231e-7, 1.0000280653614764),
+            (-0.012152181790312257, -0.86782649757545114),
+            (-1.427312173217689e-12, -1.0000000000007209),
+            (2.9763798658865338e+14, -1.1502616414985815e+14),
+            (-9.1868322696144983e+5, -8.9639058842697168e+5),
+            (1.7125235116910562e+215, 1.375674831233068e+215),
+        ];
+        check(Error::imag_error, &y);
+    }
+
+    #[test]
+    fn dawson() {
+        // References: mpmath sqrt(pi) / 2 * exp(-z**2) * erfi(z)
+        let y: [(f64, f64); 10] = [
+            (0.10744242521420304, 0.20122986983108907),
+            (0.3767710885486071, -0.36226465745593082),
+            (0.99037309232236139, -0.63887305156444329),
+            (-0.27133655022167483, -0.10027272975386935),
+            (789.26571742518951, -5.5368879210064106e+3),
+            (-0.58468903007636988, -0.49975595004343307),
+            (-5.574952495532744e+10, 2.5514191247899122e+10),
+            (0.083914979664022121, -7.2004923729458528e-3),
+            (-0.1292502137490106, 3.4754921229838821e-3),
+            (0.011537431378422189, 7.6975433252293594e-3),
+        ];
+        check(Error::dawson, &y);
+    }
+
+    #[test]
+    fn repeated_compl_error() {
+        // References: forward recurrence of mpmath erfc(z) with n = 3 and sufficient dps
+        let y: [(f64, f64); 10] = [
+            (0.062368807285160255, -0.03849584968538347),
+            (0.016270980479894368, 0.041176859663971735),
+            (-5.569370077331365e-3, 4.785865658262454e-3),
+            (3.1666275082430184, -2.2083148349319512),
+            (-3.1631197956594828, -8.905315229689778),
+            (-9.174378698267954e-5, 1.8819263341864168e-4),
+            (-2397432.284679565, -9460720.10346503),
+            (1.4068693196370053e-20, -4.1390016006093565e-22),
+            (23.29333333334839, 1.6496666666481496),
+            (-1.8880129742611825e-225, 2.2909456254106574e-225),
+        ];
+        check(|z| z.repeated_compl_error(3), &y);
+    }
+
+    #[test]
+    fn inv_compl_error() {
+        let y: [(f64, f64); 6] = [
+            (0.5, 0.5),
+            (1.999, 1e-3),
+            (1e-100, 1e-100),
+            (0.0, 1e-30),
+            (-3.0, 2.0),
+            (0.7, -0.1),
+        ];
+        for y in y.iter() {
+            let y = Complex::new(y.0, y.1);
+            let z = y.inv_compl_error().compl_error();
+            assert!((z - y).norm() <= 1e-14 * y.norm(), "{:?} {:?}", z, y);
+        }
+    }
+
+    #[test]
+    fn inv_error() {
+        let y: [(f64, f64); 6] = [
+            (0.9, 0.3),
+            (0.1, 2.0),
+            (2.0, -1.0),
+            (-0.99, 0.01),
+            (1e-3, -1e-3),
+            (0.5, 0.0),
+        ];
+        for y in y.iter() {
+            let y = Complex::new(y.0, y.1);
+            let z = y.inv_error().error();
+            assert!((z - y).norm() <= 1e-14 * y.norm(), "{:?} {:?}", z, y);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+#![allow(unstable_name_collisions)]
+
+use num_complex::Complex;
+
+use crate::faddeeva::Faddeeva;
+#[allow(unused_imports)]
+use crate::primitive::Primitive;
+
+/// Fresnel integrals.
+pub trait Fresnel
+where
+    Self: Sized,
+{
+    /// Compute the Fresnel integrals S(x) = ∫₀ˣ sin(πt² / 2) dt and C(x) = ∫₀ˣ cos(πt² / 2) dt.
+    ///
+    /// The integrals are computed via their Maclaurin series for |x| < 1 and via the auxiliary
+    /// functions otherwise. The phase πx² / 2 is reduced modulo 2π without rounding errors, so the
+    /// integrals remain accurate for large arguments. The result is (S(x), C(x)).
+    fn fresnel(self) -> (Self, Self);
+
+    /// Compute the Fresnel integrals with unit normalization, S₁(x) = ∫₀ˣ sin(t²) dt and
+    /// C₁(x) = ∫₀ˣ cos(t²) dt.
+    ///
+    /// The integrals relate to the ones of `fresnel` via S₁(x) = √(π / 2) S(x √(2 / π)), and
+    /// similarly for C₁, except that the phase x² is computed from x directly. The result is
+    /// (S₁(x), C₁(x)).
+    fn fresnel_unit(self) -> (Self, Self);
+
+    /// Compute the auxiliary functions f(x) and g(x) of the Fresnel integrals.
+    ///
+    /// The functions are defined by C(x) = 1/2 + f(x) sin(πx² / 2) - g(x) cos(πx² / 2) and
+    /// S(x) = 1/2 - f(x) cos(πx² / 2) - g(x) sin(πx² / 2). They are computed via the asymptotic
+    /// expansions for x ≥ 5.5 and as g(x) + i f(x) = (1 + i) / 2 w((1 + i) √π x / 2) otherwise,
+    /// where w is the Faddeeva function. The definitions hold for all real x, and the result is
+    /// (f(x), g(x)).
+    fn fresnel_aux(self) -> (Self, Self);
+}
+
+impl Fresnel for f64 {
+    fn fresnel(self) -> (Self, Self) {
+        use core::f64::consts::FRAC_PI_2;
+
+        let x = self.abs();
+        let (s, c) = if x < 1.0 {
+            series(x, FRAC_PI_2)
+        } else if x > 1e17 {
+            (0.5, 0.5)
+        } else {
+            let (f, g) = x.fresnel_aux();
+            let (sin, cos) = phase_half_pi(x);
+            (0.5 - f * cos - g * sin, 0.5 + f * sin - g * cos)
+        };
+        if self < 0.0 {
+            (-s, -c)
+        } else {
+            (s, c)
+        }
+    }
+
+    fn fresnel_unit(self) -> (Self, Self) {
+        const SQRT_FRAC_2_PI: f64 = 0.797884560802865355879892119868763737;
+        const SQRT_FRAC_PI_2: f64 = 1.253314137315500251207882642405522627;
+
+        let x = self.abs();
+        let y = x * SQRT_FRAC_2_PI;
+        let (s, c) = if y < 1.0 {
+            series(x, 1.0)
+        } else if x > 1e17 {
+            (0.5 * SQRT_FRAC_PI_2, 0.5 * SQRT_FRAC_PI_2)
+        } else {
+            let (f, g) = y.fresnel_aux();
+            let (sin, cos) = phase_unit(x);
+            (
+                SQRT_FRAC_PI_2 * (0.5 - f * cos - g * sin),
+                SQRT_FRAC_PI_2 * (0.5 + f * sin - g * cos),
+            )
+        };
+        if self < 0.0 {
+            (-s, -c)
+        } else {
+            (s, c)
+        }
+    }
+
+    fn fresnel_aux(self) -> (Self, Self) {
+        use core::f64::consts::PI;
+
+        const FRAC_SQRT_PI_2: f64 = 0.886226925452758013649083741671;
+
+        let x = self;
+        if x >= 5.5 {
+            // f(x) ~ 1 / (πx) Σ (-1)ᵐ (4m - 1)!! / (πx²)²ᵐ
+            // g(x) ~ 1 / (π²x³) Σ (-1)ᵐ (4m + 1)!! / (πx²)²ᵐ
+            let u = 1.0 / (PI * x * x * PI * x * x);
+            let (mut f, mut g) = (1.0, 1.0);
+            let (mut f_term, mut g_term) = (1.0, 1.0);
+            for m in 1..50 {
+                let m = m as f64;
+                f_term *= -(4.0 * m - 3.0) * (4.0 * m - 1.0) * u;
+                g_term *= -(4.0 * m - 1.0) * (4.0 * m + 1.0) * u;
+                f += f_term;
+                g += g_term;
+                if g_term.abs() <= f64::EPSILON * g {
+                    break;
+                }
+            }
+            return (f / (PI * x), g / (PI * PI * x * x * x));
+        }
+        let y = FRAC_SQRT_PI_2 * x;
+        let w = Complex::new(y, y).faddeeva();
+        (0.5 * (w.re + w.im), 0.5 * (w.re - w.im))
+    }
+}
+
+impl Fresnel for f32 {
+    #[inline]
+    fn fresnel(self) -> (Self, Self) {
+        let (s, c) = (self as f64).fresnel();
+        (s as f32, c as f32)
+    }
+
+    #[inline]
+    fn fresnel_unit(self) -> (Self, Self) {
+        let (s, c) = (self as f64).fresnel_unit();
+        (s as f32, c as f32)
+    }
+
+    #[inline]
+    fn fresnel_aux(self) -> (Self, Self) {
+        let (f, g) = (self as f64).fresnel_aux();
+        (f as f32, g as f32)
+    }
+}
+
+// Compute ∫₀ˣ sin(a t²) dt and ∫₀ˣ cos(a t²) dt via their Maclaurin series, whose terms are
+// (a x²)ᵏ / k! x / (2k + 1) with odd k contributing to the former and even k to the latter.
+fn series(x: f64, a: f64) -> (f64, f64) {
+    let u = a * x * x;
+    let mut p = x;
+    let (mut s, mut c) = (0.0, x);
+    for k in 1..60 {
+        p *= u / k as f64;
+        let term = p / (2 * k + 1) as f64;
+        match k % 4 {
+            0 => c += term,
+            1 => s += term,
+            2 => c -= term,
+            _ => s -= term,
+        }
+        if term <= f64::EPSILON * s {
+            break;
+        }
+    }
+    (s, c)
+}
+
+// Split x into a high part with 26 significant bits and a low part, so that x² = h² + 2hl + l²
+// where the first two terms are exact.
+fn split(x: f64) -> (f64, f64) {
+    let c = 134217729.0 * x;
+    let high = c - (c - x);
+    (high, x - high)
+}
+
+// Compute sin(πx² / 2) and cos(πx² / 2) by reducing x² modulo 4 exactly.
+fn phase_half_pi(x: f64) -> (f64, f64) {
+    use core::f64::consts::PI;
+
+    let modulo = |value: f64, modulus: f64| value - modulus * (value / modulus).floor();
+    let (high, low) = split(x);
+    let t = 0.5 * (modulo(high * high, 4.0) + modulo(2.0 * high * low, 4.0) + low * low);
+    let angle = PI * modulo(t, 2.0);
+    (angle.sin(), angle.cos())
+}
+
+// Compute sin(x²) and cos(x²) via the angle-addition formulas applied to the exact parts of x².
+fn phase_unit(x: f64) -> (f64, f64) {
+    let (high, low) = split(x);
+    let (mut sin, mut cos) = (0.0, 1.0);
+    for angle in [high * high, 2.0 * high * low, low * low] {
+        let (s, c) = (angle.sin(), angle.cos());
+        (sin, cos) = (sin * c + cos * s, cos * c - sin * s);
+    }
+    (sin, cos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fresnel;
+
+    #[test]
+    fn fresnel() {
+        let x: [f64; 12] = [
+            -3.5, -0.4, 1e-05, 0.3, 0.99, 1.01, 2.0, 5.4, 5.6, 10.0, 1000.5, 123456.789,
+        ];
+        // References:
+        // (mpmath.fresnels(x), mpmath.fresnelc(x)) with dps = 50
+        let y: [(f64, f64); 12] = [
+            (-0.41524801197243752, -0.53257243502800085),
+            (-0.033359432660613186, -0.39748075917235946),
+            (5.23598775598299e-16, 1.0000000000000001e-5),
+            (0.014116998006576584, 0.2994009760520472),
+            (0.42826077993226196, 0.77973685710739855),
+            (0.44825749017714233, 0.77973581021989375),
+            (0.34341567836369824, 0.48825340607534075),
+            (0.51403198870191517, 0.55723378101789083),
+            (0.47003880651486132, 0.451713624601016),
+            (0.46816997858488224, 0.49989869420551572),
+            (0.49970606693895422, 0.50012175095081008),
+            (0.50000246370825018, 0.49999923985222636),
+        ];
+        for i in 0..x.len() {
+            let (s, c) = x[i].fresnel();
+            assert::close(s, y[i].0, 1e-15 * y[i].0.abs());
+            assert::close(c, y[i].1, 1e-15 * y[i].1.abs());
+        }
+    }
+
+    #[test]
+    fn fresnel_unit() {
+        let x: [f64; 7] = [-2.0, 0.1, 1.2, 1.3, 4.0, 7.5, 300.25];
+        // References:
+        // sqrt(pi / 2) * (mpmath.fresnels(x * r), mpmath.fresnelc(x * r)) with r = sqrt(2 / pi)
+        let y: [(f64, f64); 7] = [
+            (-0.80477648934375611, -0.46146146243321637),
+            (3.3333095238852818e-4, 0.099999000004629624),
+            (0.49611578848265651, 0.97394455407622387),
+            (0.59585276867892963, 0.97468907775315956),
+            (0.74713384464811466, 0.59446032749782298),
+            (0.56313100359345988, 0.60647953475514352),
+            (0.62587260576611767, 0.62518813302267725),
+        ];
+        for i in 0..x.len() {
+            let (s, c) = x[i].fresnel_unit();
+            assert::close(s, y[i].0, 1e-15 * y[i].0.abs());
+            assert::close(c, y[i].1, 1e-15 * y[i].1.abs());
+        }
+    }
+
+    #[test]
+    fn fresnel_aux() {
+        let x: [f64; 8] = [-2.0, 0.0, 0.5, 3.0, 5.49, 5.51, 20.0, 10000.0];
+        // References:
+        // f = (C - 1/2) sin(t) + (1/2 - S) cos(t) and g = (1/2 - C) cos(t) + (1/2 - S) sin(t) with
+        // t = pi * x**2 / 2 and S and C given by mpmath with dps = 50
+        let y: [(f64, f64); 8] = [
+            (0.84341567836369824, 0.98825340607534075),
+            (0.5, 0.5),
+            (0.39920505852570224, 0.17364269961323775),
+            (0.10572078929768563, 3.6870010326249639e-3),
+            (0.057960617387931803, 6.1130938383860013e-4),
+            (0.057750511021010371, 6.0469122354376195e-4),
+            (0.015915464074046107, 1.2665027655611813e-5),
+            (3.1830988618379066e-5, 1.0132118364233776e-13),
+        ];
+        for i in 0..x.len() {
+            let (f, g) = x[i].fresnel_aux();
+            assert::close(f, y[i].0, 1e-14 * y[i].0.abs());
+            assert::close(g, y[i].1, 1e-14 * y[i].1.abs());
+        }
+    }
+}
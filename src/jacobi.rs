@@ -0,0 +1,420 @@
+#![allow(unstable_name_collisions)]
+
+#[allow(unused_imports)]
+use crate::primitive::Primitive;
+
+#[cfg(feature = "elliptic")]
+mod inverse;
+
+#[cfg(feature = "elliptic")]
+pub use self::inverse::InverseJacobi;
+
+/// Jacobi elliptic functions.
+pub trait Jacobi: Sized {
+    /// Compute the Jacobi elliptic functions sn(u|m), cn(u|m), and dn(u|m).
+    ///
+    /// For 0 ≤ m ≤ 1, the functions are computed via the arithmetic–geometric mean and the
+    /// descending Landen transformation, with sn = tanh u and cn = dn = sech u for m = 1. For
+    /// m < 0, the functions are reduced to the parameter -m / (1 - m) ∈ (0, 1) via the
+    /// negative-parameter transformation, and for m > 1, to the parameter 1 / m via the
+    /// reciprocal-parameter transformation. The functions are valid for all real u.
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: elliptic parameter (m)
+    /// - `u`: argument (u)
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use special::Jacobi;
+    ///
+    /// let m = 0.5;
+    /// let (sn, cn, dn) = m.jacobi_elliptic(0.75);
+    /// assert::close(sn, 0.6585147441299967, 1e-15);
+    /// assert::close(cn, 0.7525678253575587, 1e-15);
+    /// assert::close(dn, 0.8849741046390581, 1e-15);
+    /// ```
+    fn jacobi_elliptic(self, u: Self) -> (Self, Self, Self);
+
+    /// Compute the Jacobi amplitude am(u|m).
+    ///
+    /// The amplitude is the inverse of the incomplete elliptic integral of the first kind with
+    /// respect to the angle, that is, φ = am(u|m) if u = F(φ|m), and it is computed as the angle
+    /// of the descending Landen transformation. For 0 ≤ m ≤ 1, the amplitude increases
+    /// monotonically and continues quasi-periodically with am(u + 2K|m) = am(u|m) + π where K is
+    /// the complete elliptic integral of the first kind. For m < 0, it is obtained via the
+    /// negative-parameter transformation, and for m > 1, where it is periodic and bounded by
+    /// arcsin(1 / √m) in absolute value, as arcsin sn(u|m). Away from m = 1, composing the
+    /// function with the incomplete integral recovers the angle to a few units in the last place.
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: elliptic parameter (m)
+    /// - `u`: argument (u)
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use special::Jacobi;
+    ///
+    /// let m = 0.5;
+    /// assert::close(m.jacobi_amplitude(0.826017876249245), core::f64::consts::FRAC_PI_4, 1e-15);
+    /// ```
+    fn jacobi_amplitude(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function sn(u|m).
+    fn jacobi_sn(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function cn(u|m).
+    fn jacobi_cn(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function dn(u|m).
+    fn jacobi_dn(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function ns(u|m) = 1 / sn(u|m).
+    fn jacobi_ns(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function nc(u|m) = 1 / cn(u|m).
+    fn jacobi_nc(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function nd(u|m) = 1 / dn(u|m).
+    fn jacobi_nd(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function sc(u|m) = sn(u|m) / cn(u|m).
+    fn jacobi_sc(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function sd(u|m) = sn(u|m) / dn(u|m).
+    fn jacobi_sd(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function cd(u|m) = cn(u|m) / dn(u|m).
+    fn jacobi_cd(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function cs(u|m) = cn(u|m) / sn(u|m).
+    fn jacobi_cs(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function ds(u|m) = dn(u|m) / sn(u|m).
+    fn jacobi_ds(self, u: Self) -> Self;
+
+    /// Compute the Jacobi elliptic function dc(u|m) = dn(u|m) / cn(u|m).
+    fn jacobi_dc(self, u: Self) -> Self;
+}
+
+// The number of steps of the arithmetic–geometric mean, which converges quadratically and reaches
+// double precision in at most six steps for 0 < m < 1 - 1e-300.
+const AGM_STEPS: usize = 16;
+
+const FRAC_1_PI: f64 = core::f64::consts::FRAC_1_PI;
+const PI: f64 = core::f64::consts::PI;
+
+// Compute am, sn, cn, and dn for 0 ≤ m ≤ 1 via the descending Landen transformation.
+fn landen(m: f64, u: f64) -> (f64, f64, f64, f64) {
+    if m == 0.0 {
+        return (u, u.sin(), u.cos(), 1.0);
+    }
+    if m == 1.0 {
+        // am(u|1) is the Gudermannian function.
+        let sech = 1.0 / u.cosh();
+        return (2.0 * (0.5 * u).tanh().atan(), u.tanh(), sech, sech);
+    }
+    let mut a = [0.0; AGM_STEPS + 1];
+    let mut c = [0.0; AGM_STEPS + 1];
+    a[0] = 1.0;
+    c[0] = m.sqrt();
+    let mut b = (1.0 - m).sqrt();
+    let mut n = 0;
+    while n < AGM_STEPS && c[n].abs() > f64::EPSILON * a[n] {
+        let (previous_a, previous_b) = (a[n], b);
+        n += 1;
+        a[n] = 0.5 * (previous_a + previous_b);
+        c[n] = 0.5 * (previous_a - previous_b);
+        b = (previous_a * previous_b).sqrt();
+    }
+    if n == 0 {
+        // m is too small for the descending transformation to take a single step.
+        let sn = u.sin();
+        return (u, sn, u.cos(), (1.0 - m * sn * sn).sqrt());
+    }
+    let mut phi = (1u64 << n) as f64 * a[n] * u;
+    let mut previous = phi;
+    for k in (1..=n).rev() {
+        previous = phi;
+        phi = 0.5 * (phi + (c[k] / a[k] * phi.sin()).asin());
+    }
+    let (sn, cn) = (phi.sin(), phi.cos());
+    (phi, sn, cn, cn / (previous - phi).cos())
+}
+
+// Compute am, sn, cn, and dn for any real m by reducing to 0 ≤ m ≤ 1.
+fn elliptic(m: f64, u: f64) -> (f64, f64, f64, f64) {
+    if m < 0.0 {
+        // sn(u|m) = sd(v|μ) / √(1 - m), cn(u|m) = cd(v|μ), and dn(u|m) = nd(v|μ) with
+        // μ = -m / (1 - m) and v = u √(1 - m), so that tan am(u|m) = tan am(v|μ) / √(1 - m)
+        // where both amplitudes lie in the same half-period.
+        let scale = (1.0 - m).sqrt();
+        let (phi, sn, cn, dn) = landen(-m / (1.0 - m), u * scale);
+        let turns = (phi * FRAC_1_PI).round();
+        let reduced = phi - turns * PI;
+        let phi = (reduced.tan() / scale).atan() + turns * PI;
+        (phi, sn / (scale * dn), cn / dn, 1.0 / dn)
+    } else if m > 1.0 {
+        // sn(u|m) = sn(v|1 / m) / √m, cn(u|m) = dn(v|1 / m), and dn(u|m) = cn(v|1 / m) with
+        // v = u √m, so that am(u|m) = arcsin sn(u|m) since cn(u|m) > 0.
+        let scale = m.sqrt();
+        let (_, sn, cn, dn) = landen(1.0 / m, u * scale);
+        let sn = sn / scale;
+        (sn.asin(), sn, dn, cn)
+    } else {
+        landen(m, u)
+    }
+}
+
+macro_rules! implement {
+    ($kind:ident) => {
+        impl Jacobi for $kind {
+            #[inline]
+            fn jacobi_elliptic(self, u: Self) -> (Self, Self, Self) {
+                let (_, sn, cn, dn) = elliptic(self as f64, u as f64);
+                (sn as $kind, cn as $kind, dn as $kind)
+            }
+
+            #[inline]
+            fn jacobi_amplitude(self, u: Self) -> Self {
+                elliptic(self as f64, u as f64).0 as $kind
+            }
+
+            #[inline]
+            fn jacobi_sn(self, u: Self) -> Self {
+                self.jacobi_elliptic(u).0
+            }
+
+            #[inline]
+            fn jacobi_cn(self, u: Self) -> Self {
+                self.jacobi_elliptic(u).1
+            }
+
+            #[inline]
+            fn jacobi_dn(self, u: Self) -> Self {
+                self.jacobi_elliptic(u).2
+            }
+
+            #[inline]
+            fn jacobi_ns(self, u: Self) -> Self {
+                1.0 / self.jacobi_elliptic(u).0
+            }
+
+            #[inline]
+            fn jacobi_nc(self, u: Self) -> Self {
+                1.0 / self.jacobi_elliptic(u).1
+            }
+
+            #[inline]
+            fn jacobi_nd(self, u: Self) -> Self {
+                1.0 / self.jacobi_elliptic(u).2
+            }
+
+            #[inline]
+            fn jacobi_sc(self, u: Self) -> Self {
+                let (sn, cn, _) = self.jacobi_elliptic(u);
+                sn / cn
+            }
+
+            #[inline]
+            fn jacobi_sd(self, u: Self) -> Self {
+                let (sn, _, dn) = self.jacobi_elliptic(u);
+                sn / dn
+            }
+
+            #[inline]
+            fn jacobi_cd(self, u: Self) -> Self {
+                let (_, cn, dn) = self.jacobi_elliptic(u);
+                cn / dn
+            }
+
+            #[inline]
+            fn jacobi_cs(self, u: Self) -> Self {
+                let (sn, cn, _) = self.jacobi_elliptic(u);
+                cn / sn
+            }
+
+            #[inline]
+            fn jacobi_ds(self, u: Self) -> Self {
+                let (sn, _, dn) = self.jacobi_elliptic(u);
+                dn / sn
+            }
+
+            #[inline]
+            fn jacobi_dc(self, u: Self) -> Self {
+                let (_, cn, dn) = self.jacobi_elliptic(u);
+                dn / cn
+            }
+        }
+    };
+}
+
+implement!(f32);
+implement!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::Jacobi;
+
+    #[test]
+    fn jacobi_elliptic() {
+        let x: [(f64, f64); 13] = [
+            (0.5, 0.75),
+            (0.0, 1.3),
+            (1.0, -2.0),
+            (0.3, 10.0),
+            (0.9, -3.7),
+            (0.999999, 5.0),
+            (1e-10, 2.0),
+            (0.7, 100.0),
+            (-0.5, 1.2),
+            (-5.0, 0.8),
+            (2.0, 0.4),
+            (10.0, -1.1),
+            (0.99, 1e-3),
+        ];
+        // References: mpmath.ellipfun(kind, u, m=m) for kind in ("sn", "cn", "dn")
+        let y: [(f64, f64, f64); 13] = [
+            (
+                0.65851474412999668,
+                0.75256782535755872,
+                0.88497410463905807,
+            ),
+            (0.96355818541719298, 0.26749882862458736, 1.0),
+            (
+                -0.96402758007581688,
+                0.26580222883407969,
+                0.26580222883407969,
+            ),
+            (
+                0.27848731626489937,
+                -0.96043990685496504,
+                0.98829825680503564,
+            ),
+            (
+                -0.91168182649513791,
+                -0.41089688151468034,
+                0.50194882459732915,
+            ),
+            (
+                0.99990945384100706,
+                0.013456749956024364,
+                0.013493848164807216,
+            ),
+            (
+                0.9092974268504258,
+                -0.41614683649307553,
+                0.99999999995865891,
+            ),
+            (
+                0.36743921506381824,
+                0.93004753815774661,
+                0.95157337933724249,
+            ),
+            (0.96555884600358794, 0.26018477070001534, 1.2108476132643159),
+            (0.93106005293797418, 0.36486597241060548, 2.3096242358626904),
+            (
+                0.37057236814053485,
+                0.92880359601517258,
+                0.85167613559464717,
+            ),
+            (
+                0.079263332645754373,
+                0.99685371248638508,
+                -0.96807708421327942,
+            ),
+            (
+                0.00099999966833346535,
+                0.99999950000020667,
+                0.99999950500020584,
+            ),
+        ];
+        for (&(m, u), &(sn, cn, dn)) in x.iter().zip(y.iter()) {
+            let tolerance = 1e-14 * u.abs().max(1.0);
+            let value = m.jacobi_elliptic(u);
+            assert::close(value.0, sn, tolerance);
+            assert::close(value.1, cn, tolerance);
+            assert::close(value.2, dn, tolerance);
+        }
+
+        // References: mpmath.ellipfun(kind, 2, m=1e-40) for kind in ("sn", "cn", "dn")
+        let value = 1e-40f64.jacobi_elliptic(2.0);
+        assert::close(value.0, 0.9092974268256817, 1e-15);
+        assert::close(value.1, -0.41614683654714239, 1e-15);
+        assert_eq!(value.2, 1.0);
+    }
+
+    #[test]
+    fn jacobi_amplitude() {
+        let x: [(f64, f64); 10] = [
+            (0.5, 0.75),
+            (0.0, 1.3),
+            (1.0, -2.0),
+            (0.3, 10.0),
+            (0.9, -3.7),
+            (0.999999, 20.0),
+            (-0.5, 1.2),
+            (-5.0, -0.8),
+            (2.0, 0.4),
+            (10.0, -1.1),
+        ];
+        // References: mpmath.findroot(lambda phi: mpmath.ellipf(phi, m) - u, u) for m ≤ 1 and
+        // mpmath.asin(mpmath.ellipfun("sn", u, m=m)) for m > 1
+        let y: [f64; 10] = [
+            0.71884346844318058,
+            1.3,
+            -1.3017603360460151,
+            9.1425592025177381,
+            -1.9942339370673979,
+            4.6464594497209972,
+            1.3075827674110811,
+            -1.1973074793861013,
+            0.37962518737876416,
+            0.079346565816636809,
+        ];
+        for (&(m, u), &y) in x.iter().zip(y.iter()) {
+            assert::close(m.jacobi_amplitude(u), y, 1e-15 * y.abs().max(1.0));
+        }
+    }
+
+    #[cfg(feature = "elliptic")]
+    #[test]
+    fn jacobi_amplitude_inverse() {
+        use crate::Elliptic;
+
+        for &m in [1e-8, 0.1, 0.5, 0.9, 0.99, -0.5, -5.0].iter() {
+            for i in -100..100 {
+                let phi = i as f64 * 0.0713 + 1e-3;
+                let value = m.jacobi_amplitude(m.inc_elliptic_f(phi));
+                assert::close(value, phi, 8.0 * f64::EPSILON * phi.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn jacobi_derived() {
+        let (m, u) = (0.5f64, 0.75);
+        let (sn, cn, dn) = m.jacobi_elliptic(u);
+        assert_eq!(m.jacobi_sn(u), sn);
+        assert_eq!(m.jacobi_cn(u), cn);
+        assert_eq!(m.jacobi_dn(u), dn);
+        // References: mpmath.ellipfun(kind, 0.75, m=0.5)
+        assert::close(m.jacobi_ns(u), 1.5185688838617577, 1e-15);
+        assert::close(m.jacobi_nc(u), 1.3287838867212822, 1e-15);
+        assert::close(m.jacobi_nd(u), 1.1299765662723611, 1e-15);
+        assert::close(m.jacobi_sc(u), 0.87502378116832765, 1e-15);
+        assert::close(m.jacobi_sd(u), 0.74410622941173609, 1e-15);
+        assert::close(m.jacobi_cd(u), 0.8503840071845921, 1e-15);
+        assert::close(m.jacobi_cs(u), 1.1428260825834981, 1e-15);
+        assert::close(m.jacobi_ds(u), 1.3438941383282928, 1e-15);
+        assert::close(m.jacobi_dc(u), 1.1759393304099743, 1e-15);
+
+        let (sn, cn, dn) = 0.5f32.jacobi_elliptic(0.75);
+        assert::close(sn, 0.6585147, 1e-6);
+        assert::close(cn, 0.7525678, 1e-6);
+        assert::close(dn, 0.8849741, 1e-6);
+    }
+}
@@ -0,0 +1,435 @@
+#![allow(unstable_name_collisions)]
+
+#[allow(unused_imports)]
+use crate::primitive::Primitive;
+use crate::Elliptic;
+
+/// Inverse Jacobi elliptic functions.
+///
+/// The functions are computed via Carlson’s symmetric integral of the first kind (RF) for
+/// 0 ≤ m ≤ 1, and K below is the complete elliptic integral of the first kind. Each function
+/// returns the value in its principal range given in its description, and NaN is returned if x
+/// is outside the domain or m is outside [0, 1]. Infinite x is handled via the limit.
+pub trait InverseJacobi: Sized {
+    /// Compute the inverse Jacobi elliptic function arcsn(x|m).
+    ///
+    /// The domain is -1 ≤ x ≤ 1, and the range is [-K, K].
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: elliptic parameter (m)
+    /// - `x`: argument (x)
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use special::InverseJacobi;
+    ///
+    /// let m = 0.5;
+    /// assert::close(m.jacobi_arcsn(0.6585147441299967), 0.75, 1e-15);
+    /// ```
+    fn jacobi_arcsn(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arccn(x|m).
+    ///
+    /// The domain is -1 ≤ x ≤ 1, and the range is [0, 2K].
+    fn jacobi_arccn(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcdn(x|m).
+    ///
+    /// The domain is √(1 - m) ≤ x ≤ 1, and the range is [0, K].
+    fn jacobi_arcdn(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcns(x|m).
+    ///
+    /// The domain is |x| ≥ 1, and the range is [-K, 0) ∪ (0, K].
+    fn jacobi_arcns(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcnc(x|m).
+    ///
+    /// The domain is |x| ≥ 1, and the range is [0, K) ∪ (K, 2K].
+    fn jacobi_arcnc(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcnd(x|m).
+    ///
+    /// The domain is 1 ≤ x ≤ 1 / √(1 - m), and the range is [0, K].
+    fn jacobi_arcnd(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcsc(x|m).
+    ///
+    /// The domain is the real line, and the range is (-K, K).
+    fn jacobi_arcsc(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcsd(x|m).
+    ///
+    /// The domain is |x| ≤ 1 / √(1 - m), and the range is [-K, K].
+    fn jacobi_arcsd(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arccd(x|m).
+    ///
+    /// The domain is -1 ≤ x ≤ 1, and the range is [0, 2K].
+    fn jacobi_arccd(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arccs(x|m).
+    ///
+    /// The domain is the real line, and the range is [-K, 0) ∪ (0, K] with arccs(0|m) = K.
+    fn jacobi_arccs(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcds(x|m).
+    ///
+    /// The domain is |x| ≥ √(1 - m), and the range is [-K, 0) ∪ (0, K].
+    fn jacobi_arcds(self, x: Self) -> Self;
+
+    /// Compute the inverse Jacobi elliptic function arcdc(x|m).
+    ///
+    /// The domain is |x| ≥ 1, and the range is [0, K) ∪ (K, 2K].
+    fn jacobi_arcdc(self, x: Self) -> Self;
+}
+
+// Return NaN from the enclosing function unless 0 ≤ m ≤ 1.
+macro_rules! parameter {
+    ($m:expr) => {{
+        let m: f64 = $m;
+        if !(0.0..=1.0).contains(&m) {
+            return f64::NAN;
+        }
+        m
+    }};
+}
+
+// Beyond |x| = 2, the arguments of RF are divided by x² using RF(λx, λy, λz) = RF(x, y, z) / √λ,
+// so that they do not overflow, and t = 1 / x² below. For m = 1, where sc and sd reduce to sinh
+// and nc and nd to cosh, the functions are computed directly, since the arguments would otherwise
+// differ by more than the floating-point range.
+impl InverseJacobi for f64 {
+    fn jacobi_arcsn(self, x: Self) -> Self {
+        let m = parameter!(self);
+        x * rf((1.0 - x) * (1.0 + x), 1.0 - m * x * x, 1.0)
+    }
+
+    fn jacobi_arccn(self, x: Self) -> Self {
+        let m = parameter!(self);
+        let y = x.abs();
+        let value = ((1.0 - y) * (1.0 + y)).sqrt() * rf(y * y, 1.0 - m + m * y * y, 1.0);
+        reflect(m, x, value)
+    }
+
+    fn jacobi_arcdn(self, x: Self) -> Self {
+        let m = parameter!(self);
+        if x < 0.0 {
+            return f64::NAN;
+        }
+        if x == 1.0 {
+            return 0.0;
+        }
+        let square = x * x;
+        ((1.0 - x) * (1.0 + x)).sqrt() * rf(square - (1.0 - m), m * square, m)
+    }
+
+    fn jacobi_arcns(self, x: Self) -> Self {
+        let m = parameter!(self);
+        let y = x.abs();
+        if y > 2.0 {
+            let t = (y * y).recip();
+            return rf(1.0 - t, 1.0 - m * t, 1.0) / x;
+        }
+        x.signum() * rf((y - 1.0) * (y + 1.0), y * y - m, y * y)
+    }
+
+    fn jacobi_arcnc(self, x: Self) -> Self {
+        let m = parameter!(self);
+        let y = x.abs();
+        let value = if m == 1.0 {
+            y.acosh()
+        } else if y > 2.0 {
+            let r = y.recip();
+            let t = r * r;
+            ((1.0 - r) * (1.0 + r)).sqrt() * rf(t, 1.0 - m + m * t, 1.0)
+        } else {
+            let square = y * y;
+            ((y - 1.0) * (y + 1.0)).sqrt() * rf(1.0, (1.0 - m) * square + m, square)
+        };
+        reflect(m, x, value)
+    }
+
+    fn jacobi_arcnd(self, x: Self) -> Self {
+        let m = parameter!(self);
+        if x < 0.0 {
+            return f64::NAN;
+        }
+        if x == 1.0 {
+            return 0.0;
+        }
+        if m == 1.0 {
+            return x.acosh();
+        }
+        if x > 2.0 {
+            let r = x.recip();
+            let t = r * r;
+            return ((1.0 - r) * (1.0 + r)).sqrt() * rf(t - (1.0 - m), m * t, m);
+        }
+        let square = x * x;
+        ((x - 1.0) * (x + 1.0)).sqrt() * rf(1.0 - (1.0 - m) * square, m, m * square)
+    }
+
+    fn jacobi_arcsc(self, x: Self) -> Self {
+        let m = parameter!(self);
+        if m == 1.0 {
+            return x.asinh();
+        }
+        if x.abs() > 2.0 {
+            let t = (x * x).recip();
+            return x.signum() * rf(t, t + (1.0 - m), t + 1.0);
+        }
+        let square = x * x;
+        x * rf(1.0, 1.0 + (1.0 - m) * square, 1.0 + square)
+    }
+
+    fn jacobi_arcsd(self, x: Self) -> Self {
+        let m = parameter!(self);
+        if m == 1.0 {
+            return x.asinh();
+        }
+        if x.abs() > 2.0 {
+            let t = (x * x).recip();
+            return x.signum() * rf(t - (1.0 - m), t, t + m);
+        }
+        let square = x * x;
+        x * rf(1.0 - (1.0 - m) * square, 1.0, 1.0 + m * square)
+    }
+
+    fn jacobi_arccd(self, x: Self) -> Self {
+        let m = parameter!(self);
+        let y = x.abs();
+        let square = y * y;
+        let value =
+            ((1.0 - y) * (1.0 + y)).sqrt() * rf((1.0 - m) * square, 1.0 - m, 1.0 - m * square);
+        reflect(m, x, value)
+    }
+
+    fn jacobi_arccs(self, x: Self) -> Self {
+        let m = parameter!(self);
+        if x.abs() > 2.0 {
+            let t = (x * x).recip();
+            return rf(1.0, 1.0 + (1.0 - m) * t, 1.0 + t) / x;
+        }
+        let square = x * x;
+        x.signum() * rf(square, square + (1.0 - m), square + 1.0)
+    }
+
+    fn jacobi_arcds(self, x: Self) -> Self {
+        let m = parameter!(self);
+        if x.abs() > 2.0 {
+            let t = (x * x).recip();
+            return rf(1.0 - (1.0 - m) * t, 1.0, 1.0 + m * t) / x;
+        }
+        let square = x * x;
+        x.signum() * rf(square - (1.0 - m), square, square + m)
+    }
+
+    fn jacobi_arcdc(self, x: Self) -> Self {
+        let m = parameter!(self);
+        let y = x.abs();
+        let value = if y > 2.0 {
+            let r = y.recip();
+            let t = r * r;
+            ((1.0 - r) * (1.0 + r)).sqrt() * rf((1.0 - m) * t, 1.0 - m, 1.0 - m * t)
+        } else {
+            let square = y * y;
+            ((y - 1.0) * (y + 1.0)).sqrt() * rf(1.0 - m, (1.0 - m) * square, square - m)
+        };
+        reflect(m, x, value)
+    }
+}
+
+impl InverseJacobi for f32 {
+    #[inline]
+    fn jacobi_arcsn(self, x: Self) -> Self {
+        (self as f64).jacobi_arcsn(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arccn(self, x: Self) -> Self {
+        (self as f64).jacobi_arccn(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcdn(self, x: Self) -> Self {
+        (self as f64).jacobi_arcdn(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcns(self, x: Self) -> Self {
+        (self as f64).jacobi_arcns(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcnc(self, x: Self) -> Self {
+        (self as f64).jacobi_arcnc(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcnd(self, x: Self) -> Self {
+        (self as f64).jacobi_arcnd(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcsc(self, x: Self) -> Self {
+        (self as f64).jacobi_arcsc(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcsd(self, x: Self) -> Self {
+        (self as f64).jacobi_arcsd(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arccd(self, x: Self) -> Self {
+        (self as f64).jacobi_arccd(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arccs(self, x: Self) -> Self {
+        (self as f64).jacobi_arccs(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcds(self, x: Self) -> Self {
+        (self as f64).jacobi_arcds(x as f64) as f32
+    }
+
+    #[inline]
+    fn jacobi_arcdc(self, x: Self) -> Self {
+        (self as f64).jacobi_arcdc(x as f64) as f32
+    }
+}
+
+// Compute RF(x, y, z), returning NaN if any argument is negative or NaN and infinity if more than
+// one argument is zero.
+fn rf(x: f64, y: f64, z: f64) -> f64 {
+    if !(x >= 0.0 && y >= 0.0 && z >= 0.0) {
+        return f64::NAN;
+    }
+    if (x == 0.0) as u8 + (y == 0.0) as u8 + (z == 0.0) as u8 > 1 {
+        return f64::INFINITY;
+    }
+    x.elliptic_rf(y, z)
+}
+
+// Map the value at |x| to the one at x for a function that runs from 0 to 2K and whose value at -x
+// is 2K minus its value at x.
+fn reflect(m: f64, x: f64, value: f64) -> f64 {
+    if x < 0.0 {
+        let k = rf(0.0, 1.0 - m, 1.0);
+        2.0 * k - value
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InverseJacobi;
+    use crate::{Elliptic, Jacobi};
+
+    #[test]
+    fn inverse() {
+        let m = 0.7f64;
+        // References: mpmath.ellipf(phi, m) with the amplitude phi of the given value, for
+        // instance, phi = mpmath.acos(x) for arccn
+        let cases = [
+            (
+                f64::jacobi_arcsn as fn(f64, f64) -> f64,
+                -0.6,
+                -0.67605677365418588,
+            ),
+            (f64::jacobi_arccn, -0.3, 2.6136062534404966),
+            (f64::jacobi_arcdn, 0.8, 0.86363226999446149),
+            (f64::jacobi_arcns, -1.7, -0.65920038114985418),
+            (f64::jacobi_arcnc, -2.5, 2.7855217598779225),
+            (f64::jacobi_arcnd, 1.4, 1.1163042482101443),
+            (f64::jacobi_arcsc, -3.0, -1.508942038854472),
+            (f64::jacobi_arcsd, 1.2, 1.1433044629497747),
+            (f64::jacobi_arccd, -0.45, 2.5542894672966598),
+            (f64::jacobi_arccs, -0.2, -1.7201801960452559),
+            (f64::jacobi_arcds, 0.9, 1.060729725579883),
+            (f64::jacobi_arcdc, -1.3, 3.0385950643558897),
+        ];
+        for &(function, x, y) in cases.iter() {
+            assert::close(function(m, x), y, 1e-15 * y.abs());
+        }
+    }
+
+    #[test]
+    fn inverse_principal() {
+        for &m in [0.0, 0.1, 0.5, 0.9].iter() {
+            let k = m.elliptic_k();
+            for i in 1..50 {
+                let u = i as f64 / 50.0 * 2.0 * k;
+                let v = u - k;
+                let (_, cn, dn) = m.jacobi_elliptic(u);
+                assert::close(m.jacobi_arccn(cn), u, 1e-14);
+                assert::close(m.jacobi_arcnc(1.0 / cn), u, 1e-14);
+                assert::close(m.jacobi_arccd(cn / dn), u, 1e-14);
+                assert::close(m.jacobi_arcdc(dn / cn), u, 1e-14);
+                let (sn, cn, _) = m.jacobi_elliptic(v);
+                assert::close(m.jacobi_arcsc(sn / cn), v, 1e-14);
+                if v.abs() < 0.9 * k {
+                    assert::close(m.jacobi_arcsn(sn), v, 1e-14);
+                    if v != 0.0 {
+                        assert::close(m.jacobi_arcns(1.0 / sn), v, 1e-14);
+                        assert::close(m.jacobi_arccs(cn / sn), v, 1e-14);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_domain() {
+        let m = 0.5f64;
+        assert!(m.jacobi_arcsn(1.5).is_nan());
+        assert!(m.jacobi_arccn(-1.5).is_nan());
+        assert!(m.jacobi_arcdn(0.5).is_nan());
+        assert!(m.jacobi_arcns(0.5).is_nan());
+        assert!(m.jacobi_arcnd(1.5).is_nan());
+        assert!(m.jacobi_arcsd(1.5).is_nan());
+        assert!(m.jacobi_arcds(0.5).is_nan());
+        assert_eq!(m.jacobi_arcdn(1.0), 0.0);
+        assert_eq!(0.0f64.jacobi_arcnd(1.0), 0.0);
+        assert_eq!(1.0f64.jacobi_arcsn(1.0), f64::INFINITY);
+        assert::close(m.jacobi_arccs(0.0), m.elliptic_k(), 1e-15);
+        assert!((-0.5f64).jacobi_arcsn(0.5).is_nan());
+        assert!(1.5f64.jacobi_arcsc(0.5).is_nan());
+    }
+
+    #[test]
+    fn inverse_large() {
+        let m = 0.5f64;
+        let k = m.elliptic_k();
+        for &x in [1e160, f64::INFINITY].iter() {
+            assert::close(m.jacobi_arcsc(x), k, 1e-15);
+            assert::close(m.jacobi_arcsc(-x), -k, 1e-15);
+            assert::close(m.jacobi_arcnc(x), k, 1e-15);
+            assert::close(m.jacobi_arcnc(-x), k, 1e-15);
+            assert::close(m.jacobi_arcdc(x), k, 1e-15);
+            assert::close(m.jacobi_arcdc(-x), k, 1e-15);
+        }
+        assert::close(m.jacobi_arcns(-1e160), -1e-160, 1e-175);
+        assert::close(m.jacobi_arccs(1e160), 1e-160, 1e-175);
+        assert::close(m.jacobi_arcds(1e160), 1e-160, 1e-175);
+        assert_eq!(m.jacobi_arcns(f64::INFINITY), 0.0);
+        assert_eq!(m.jacobi_arccs(f64::INFINITY), 0.0);
+        assert_eq!(m.jacobi_arcds(f64::INFINITY), 0.0);
+
+        // sc(u|1) = sd(u|1) = sinh(u), and nc(u|1) = nd(u|1) = cosh(u).
+        let u = (2e160f64).ln();
+        assert::close(1.0f64.jacobi_arcsd(1e160), u, 1e-15 * u);
+        assert::close(1.0f64.jacobi_arcsd(-1e160), -u, 1e-15 * u);
+        assert::close(1.0f64.jacobi_arcsc(1e160), u, 1e-15 * u);
+        assert::close(1.0f64.jacobi_arcnc(1e160), u, 1e-15 * u);
+        assert::close(1.0f64.jacobi_arcnd(1e160), u, 1e-15 * u);
+        assert_eq!(1.0f64.jacobi_arcsd(f64::INFINITY), f64::INFINITY);
+        assert_eq!(1.0f64.jacobi_arcnd(f64::INFINITY), f64::INFINITY);
+    }
+}
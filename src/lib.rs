@@ -15,16 +15,40 @@ mod beta;
 #[cfg(feature = "elliptic")]
 mod elliptic;
 mod error;
+#[cfg(feature = "complex")]
+mod faddeeva;
+#[cfg(feature = "complex")]
+mod fresnel;
 mod gamma;
+mod jacobi;
 #[cfg(feature = "lambert_w")]
 mod lambert_w;
+mod normal;
 mod primitive;
+mod theta;
+#[cfg(feature = "complex")]
+mod voigt;
+#[cfg(feature = "elliptic")]
+mod weierstrass;
 
-pub use crate::beta::Beta;
+pub use crate::beta::{Beta, Convergence};
 #[cfg(feature = "elliptic")]
 pub use crate::elliptic::Elliptic;
 pub use crate::error::Error;
+#[cfg(feature = "complex")]
+pub use crate::faddeeva::Faddeeva;
+#[cfg(feature = "complex")]
+pub use crate::fresnel::Fresnel;
 pub use crate::gamma::Gamma;
+#[cfg(feature = "elliptic")]
+pub use crate::jacobi::InverseJacobi;
+pub use crate::jacobi::Jacobi;
 #[cfg(feature = "lambert_w")]
 pub use crate::lambert_w::LambertW;
+pub use crate::normal::{MultivariateNormal, Normal};
 pub use crate::primitive::Primitive;
+pub use crate::theta::Theta;
+#[cfg(feature = "complex")]
+pub use crate::voigt::Voigt;
+#[cfg(feature = "elliptic")]
+pub use crate::weierstrass::Weierstrass;
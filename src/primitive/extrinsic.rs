@@ -2,7 +2,13 @@ macro_rules! run {
     ($macro: ident) => {
         $macro! {
             (abs, libm::fabsf, libm::fabs, () -> Self),
+            (acos, libm::acosf, libm::acos, () -> Self),
+            (acosh, libm::acoshf, libm::acosh, () -> Self),
+            (asin, libm::asinf, libm::asin, () -> Self),
+            (asinh, libm::asinhf, libm::asinh, () -> Self),
             (atan, libm::atanf, libm::atan, () -> Self),
+            (cos, libm::cosf, libm::cos, () -> Self),
+            (cosh, libm::coshf, libm::cosh, () -> Self),
             (erf, libm::erff, libm::erf, () -> Self),
             (erfc, libm::erfcf, libm::erfc, () -> Self),
             (exp, libm::expf, libm::exp, () -> Self),
@@ -17,6 +23,7 @@ macro_rules! run {
             (sin, libm::sinf, libm::sin, () -> Self),
             (sqrt, libm::sqrtf, libm::sqrt, () -> Self),
             (tan, libm::tanf, libm::tan, () -> Self),
+            (tanh, libm::tanhf, libm::tanh, () -> Self),
             (tgamma, libm::tgammaf, libm::tgamma, () -> Self),
             (trunc, libm::truncf, libm::trunc, () -> Self),
         }
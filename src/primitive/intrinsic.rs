@@ -2,7 +2,13 @@ macro_rules! run {
     ($macro: ident) => {
         $macro! {
             (abs, Self::abs, Self::abs, () -> Self),
+            (acos, Self::acos, Self::acos, () -> Self),
+            (acosh, Self::acosh, Self::acosh, () -> Self),
+            (asin, Self::asin, Self::asin, () -> Self),
+            (asinh, Self::asinh, Self::asinh, () -> Self),
             (atan, Self::atan, Self::atan, () -> Self),
+            (cos, Self::cos, Self::cos, () -> Self),
+            (cosh, Self::cosh, Self::cosh, () -> Self),
             (erf, libm::erff, libm::erf, () -> Self),
             (erfc, libm::erfcf, libm::erfc, () -> Self),
             (exp, Self::exp, Self::exp, () -> Self),
@@ -17,6 +23,7 @@ macro_rules! run {
             (sin, Self::sin, Self::sin, () -> Self),
             (sqrt, Self::sqrt, Self::sqrt, () -> Self),
             (tan, Self::tan, Self::tan, () -> Self),
+            (tanh, Self::tanh, Self::tanh, () -> Self),
             (tgamma, libm::tgammaf, libm::tgamma, () -> Self),
             (trunc, Self::trunc, Self::trunc, () -> Self),
         }
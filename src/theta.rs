@@ -0,0 +1,464 @@
+#![allow(unstable_name_collisions)]
+
+#[allow(unused_imports)]
+use crate::primitive::Primitive;
+
+/// Jacobi theta functions and the elliptic nome.
+///
+/// The theta functions are defined with the nome q and follow the convention θ₃(z, q) =
+/// 1 + 2 Σ qⁿ² cos 2nz. For 0 ≤ q ≤ e^-π, the Fourier series is summed directly, and for
+/// e^-π < q < 1, the series obtained via the imaginary transformation, which is a sum of Gaussians
+/// in z, is summed instead, so that the functions remain accurate in the relative sense for q
+/// close to 1 where they vary over many orders of magnitude.
+pub trait Theta: Sized {
+    /// Compute the Jacobi theta function θ₁(z, q).
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: argument (z)
+    /// - `q`: nome (0 ≤ q < 1)
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use special::Theta;
+    ///
+    /// let z = 0.5;
+    /// assert::close(z.theta_1(0.1), 0.5279836054564474, 1e-15);
+    /// ```
+    fn theta_1(self, q: Self) -> Self;
+
+    /// Compute the Jacobi theta function θ₂(z, q).
+    fn theta_2(self, q: Self) -> Self;
+
+    /// Compute the Jacobi theta function θ₃(z, q).
+    fn theta_3(self, q: Self) -> Self;
+
+    /// Compute the Jacobi theta function θ₄(z, q).
+    fn theta_4(self, q: Self) -> Self;
+
+    /// Compute the derivative of θ₁(z, q) with respect to z.
+    fn theta_1_derivative(self, q: Self) -> Self;
+
+    /// Compute the derivative of θ₂(z, q) with respect to z.
+    fn theta_2_derivative(self, q: Self) -> Self;
+
+    /// Compute the derivative of θ₃(z, q) with respect to z.
+    fn theta_3_derivative(self, q: Self) -> Self;
+
+    /// Compute the derivative of θ₄(z, q) with respect to z.
+    fn theta_4_derivative(self, q: Self) -> Self;
+
+    /// Compute the elliptic nome, q(m) = exp(-π K(1 - m) / K(m)), where K is the complete
+    /// elliptic integral of the first kind.
+    ///
+    /// For m ≤ 1/2, the nome is computed via the series in λ = (1 - √k′) / (2 (1 + √k′)) with
+    /// k′ = √(1 - m), and for m > 1/2, via ln q(m) ln q(1 - m) = π².
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: elliptic parameter (0 ≤ m ≤ 1)
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use special::Theta;
+    ///
+    /// let m = 0.5;
+    /// assert::close(m.elliptic_nome(), 0.04321391826377226, 1e-16);
+    /// ```
+    fn elliptic_nome(self) -> Self;
+
+    /// Compute the inverse of the elliptic nome, m(q) = θ₂⁴(0, q) / θ₃⁴(0, q).
+    ///
+    /// For q > e^-π, the complementary parameter 1 - m is computed instead at the nome
+    /// exp(π² / ln q).
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: nome (0 ≤ q ≤ 1)
+    fn inv_elliptic_nome(self) -> Self;
+}
+
+const PI: f64 = core::f64::consts::PI;
+
+// The number of terms of either series, which is enough for the terms to decay below e^-40
+// relative to the leading one on both sides of q = e^-π.
+const TERMS: usize = 6;
+
+// Compute θₖ(z, q) and its derivative with s = -ln q.
+fn theta(kind: usize, z: f64, s: f64) -> (f64, f64) {
+    if s >= PI {
+        fourier(kind, z, s)
+    } else {
+        gaussian(kind, z, s)
+    }
+}
+
+// Compute θₖ(z, q) and its derivative via the Fourier series with s = -ln q.
+fn fourier(kind: usize, z: f64, s: f64) -> (f64, f64) {
+    let (mut value, mut derivative) = if kind > 2 { (1.0, 0.0) } else { (0.0, 0.0) };
+    for n in 0..TERMS {
+        let sign = if n & 1 == 0 { 1.0 } else { -1.0 };
+        if kind > 2 {
+            if n == 0 {
+                continue;
+            }
+            let n = n as f64;
+            let factor = 2.0 * (-s * n * n).exp();
+            let factor = if kind == 4 { sign * factor } else { factor };
+            value += factor * (2.0 * n * z).cos();
+            derivative -= 2.0 * n * factor * (2.0 * n * z).sin();
+        } else {
+            let k = (2 * n + 1) as f64;
+            let factor = 2.0 * (-0.25 * s * k * k).exp();
+            if kind == 1 {
+                value += sign * factor * (k * z).sin();
+                derivative += sign * k * factor * (k * z).cos();
+            } else {
+                value += factor * (k * z).cos();
+                derivative -= k * factor * (k * z).sin();
+            }
+        }
+    }
+    (value, derivative)
+}
+
+// Compute θₖ(z, q) and its derivative via the imaginary transformation with s = -ln q, in which
+// case θₖ(z, q) = √(π / s) Σ σₙ exp(-(z - cₙ)² / s) over all integers n with the centers cₙ = nπ
+// for θ₂ and θ₃ and cₙ = (n + 1/2) π for θ₁ and θ₄ and the signs σₙ = (-1)ⁿ for θ₁ and θ₂ and
+// σₙ = 1 otherwise. The terms at cₙ and -cₙ are paired via exp_m1 in order to retain the relative
+// accuracy of the odd parts close to zero.
+fn gaussian(kind: usize, z: f64, s: f64) -> (f64, f64) {
+    // θ₁ and θ₂ change sign and θ₃ and θ₄ repeat when z is shifted by π.
+    let turns = (z / PI).round();
+    let parity = if kind <= 2 && turns % 2.0 != 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    let z = z - turns * PI;
+    let (z, reflection) = if z < 0.0 { (-z, -1.0) } else { (z, 1.0) };
+    let (offset, start) = if kind == 1 || kind == 4 {
+        (0.5, 0)
+    } else {
+        (0.0, 1)
+    };
+    let (mut value, mut derivative) = if start == 1 {
+        let term = (-z * z / s).exp();
+        (term, -z * term)
+    } else {
+        (0.0, 0.0)
+    };
+    for n in start..(start + TERMS) {
+        let sign = if kind <= 2 && n & 1 == 1 { -1.0 } else { 1.0 };
+        let center = (n as f64 + offset) * PI;
+        let left = (-(center - z) * (center - z) / s).exp();
+        let right = (-(center + z) * (center + z) / s).exp();
+        let difference = -left * (-4.0 * center * z / s).exp_m1();
+        if kind == 1 {
+            value += sign * difference;
+            derivative += sign * ((center - z) * left + (center + z) * right);
+        } else {
+            value += sign * (left + right);
+            derivative += sign * (center * difference - z * (left + right));
+        }
+    }
+    let scale = parity * (PI / s).sqrt();
+    let derivative = 2.0 * scale / s * derivative;
+    if kind == 1 {
+        (reflection * scale * value, derivative)
+    } else {
+        (scale * value, reflection * derivative)
+    }
+}
+
+// Compute the nome for 0 ≤ m ≤ 1/2 via q = λ + 2λ⁵ + 15λ⁹ + 150λ¹³ + 1707λ¹⁷ where λ is evaluated
+// without cancellation as m / (2 (1 + k′) (1 + √k′)²).
+fn nome_series(m: f64) -> f64 {
+    let complement = (1.0 - m).sqrt();
+    let root = complement.sqrt();
+    let lambda = m / (2.0 * (1.0 + complement) * (1.0 + root) * (1.0 + root));
+    let power = lambda.powi(4);
+    lambda * (1.0 + power * (2.0 + power * (15.0 + power * (150.0 + power * 1707.0))))
+}
+
+// Compute θ₂⁴(0, q) / θ₃⁴(0, q) for 0 ≤ q ≤ e^-π.
+fn parameter_series(q: f64) -> f64 {
+    let s = -q.ln();
+    let ratio = fourier(2, 0.0, s).0 / fourier(3, 0.0, s).0;
+    let square = ratio * ratio;
+    square * square
+}
+
+impl Theta for f64 {
+    #[inline]
+    fn theta_1(self, q: Self) -> Self {
+        theta(1, self, -q.ln()).0
+    }
+
+    #[inline]
+    fn theta_2(self, q: Self) -> Self {
+        theta(2, self, -q.ln()).0
+    }
+
+    #[inline]
+    fn theta_3(self, q: Self) -> Self {
+        theta(3, self, -q.ln()).0
+    }
+
+    #[inline]
+    fn theta_4(self, q: Self) -> Self {
+        theta(4, self, -q.ln()).0
+    }
+
+    #[inline]
+    fn theta_1_derivative(self, q: Self) -> Self {
+        theta(1, self, -q.ln()).1
+    }
+
+    #[inline]
+    fn theta_2_derivative(self, q: Self) -> Self {
+        theta(2, self, -q.ln()).1
+    }
+
+    #[inline]
+    fn theta_3_derivative(self, q: Self) -> Self {
+        theta(3, self, -q.ln()).1
+    }
+
+    #[inline]
+    fn theta_4_derivative(self, q: Self) -> Self {
+        theta(4, self, -q.ln()).1
+    }
+
+    fn elliptic_nome(self) -> Self {
+        let m = self;
+        if m <= 0.5 {
+            nome_series(m)
+        } else if m < 1.0 {
+            (PI * PI / nome_series(1.0 - m).ln()).exp()
+        } else if m == 1.0 {
+            1.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn inv_elliptic_nome(self) -> Self {
+        let q = self;
+        let threshold = (-PI).exp();
+        if q <= threshold {
+            parameter_series(q)
+        } else if q < 1.0 {
+            1.0 - parameter_series((PI * PI / q.ln()).exp())
+        } else if q == 1.0 {
+            1.0
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl Theta for f32 {
+    #[inline]
+    fn theta_1(self, q: Self) -> Self {
+        (self as f64).theta_1(q as f64) as f32
+    }
+
+    #[inline]
+    fn theta_2(self, q: Self) -> Self {
+        (self as f64).theta_2(q as f64) as f32
+    }
+
+    #[inline]
+    fn theta_3(self, q: Self) -> Self {
+        (self as f64).theta_3(q as f64) as f32
+    }
+
+    #[inline]
+    fn theta_4(self, q: Self) -> Self {
+        (self as f64).theta_4(q as f64) as f32
+    }
+
+    #[inline]
+    fn theta_1_derivative(self, q: Self) -> Self {
+        (self as f64).theta_1_derivative(q as f64) as f32
+    }
+
+    #[inline]
+    fn theta_2_derivative(self, q: Self) -> Self {
+        (self as f64).theta_2_derivative(q as f64) as f32
+    }
+
+    #[inline]
+    fn theta_3_derivative(self, q: Self) -> Self {
+        (self as f64).theta_3_derivative(q as f64) as f32
+    }
+
+    #[inline]
+    fn theta_4_derivative(self, q: Self) -> Self {
+        (self as f64).theta_4_derivative(q as f64) as f32
+    }
+
+    #[inline]
+    fn elliptic_nome(self) -> Self {
+        (self as f64).elliptic_nome() as f32
+    }
+
+    #[inline]
+    fn inv_elliptic_nome(self) -> Self {
+        (self as f64).inv_elliptic_nome() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Theta;
+
+    #[test]
+    fn theta() {
+        let x: [(f64, f64); 12] = [
+            (0.5, 0.04),
+            (-2.3, 0.04),
+            (7.0, 0.04),
+            (0.001, 0.04),
+            (0.5, 0.5),
+            (-2.3, 0.5),
+            (7.0, 0.5),
+            (0.001, 0.5),
+            (0.5, 0.99),
+            (-2.3, 0.99),
+            (7.0, 0.99),
+            (0.001, 0.99),
+        ];
+        // References: mpmath.jtheta(n, z, q, derivative) for n in 1..=4
+        let y: [[(f64, f64); 4]; 12] = [
+            [
+                (0.42738374135767826, 0.78462999849925548),
+                (0.78503493360960609, -0.43309374461685611),
+                (1.043222053797129, -0.13465397998100876),
+                (0.95677368485926474, 0.13461673515840598),
+            ],
+            [
+                (-0.66615121942813756, -0.59943749349847951),
+                (-0.59476801763739739, 0.6694623890221991),
+                (0.99102280664602746, -0.15898599579294493),
+                (1.0089672109552894, 0.15899512536382647),
+            ],
+            [
+                (0.5864293523637806, 0.67666219801659486),
+                (0.67352682602020168, -0.59121864242576959),
+                (1.0109340489143815, -0.15850272505884067),
+                (0.98905609400154749, 0.15849162875775158),
+            ],
+            [
+                (0.00089013381616959058, 0.89013353090681068),
+                (0.89585782451561457, -0.00090730686574846849),
+                (1.0800049599596177, -0.00032008170646713044),
+                (0.92000527995846244, 0.00031991786690403676),
+            ],
+            [
+                (0.40276857585481431, 1.2841142293160835),
+                (1.4842160876595831, -2.1420903360836534),
+                (1.4843968624251669, -2.1407128453223713),
+                (0.41152653325340552, 1.2317849511602712),
+            ],
+            [
+                (-0.98807099127522333, -2.0832951588132467),
+                (-0.76524767523511294, 1.8676235915024144),
+                (0.76731171958118236, -1.8539257822553546),
+                (0.98903234573595593, 2.0766035065465723),
+            ],
+            [
+                (0.74228507002097685, 1.8391968674999943),
+                (1.0139933877337721, -2.1012286910978466),
+                (1.0148751182222317, -2.0950597615076105),
+                (0.74452549158238457, 1.8244086362628517),
+            ],
+            [
+                (0.00054897962009741955, 0.54898179517115791),
+                (2.1289281790021268, -0.0061430173716302517),
+                (2.1289337559220485, -0.0061425752249797365),
+                (0.12112527734195705, 0.002138679674922701),
+            ],
+            [
+                (5.0173126615848688e-49, 1.0691224744563863e-46),
+                (2.7829175836610314e-10, -2.7689796880683216e-8),
+                (2.7829175836610314e-10, -2.7689796880683216e-8),
+                (5.0173126615848688e-49, 1.0691224744563863e-46),
+            ],
+            [
+                (-1.8623259994874764e-22, -2.7024270219955132e-20),
+                (-4.3793713540026457e-30, 7.3343753136093768e-28),
+                (4.3793713540026457e-30, -7.3343753136093768e-28),
+                (1.8623259994874764e-22, 2.7024270219955132e-20),
+            ],
+            [
+                (5.4160536066957215e-31, 9.2040910396601749e-29),
+                (1.1070935053023888e-21, -1.5792126800425877e-19),
+                (1.1070935053023888e-21, -1.5792126800425877e-19),
+                (5.4160536066957215e-31, 9.2040910396601749e-29),
+            ],
+            [
+                (2.6872550267968048e-106, 2.7736792041613858e-103),
+                (17.678338157065104, -3.5179596810998359),
+                (17.678338157065104, -3.5179596810998359),
+                (8.8750468746763063e-106, 8.3823174464589716e-104),
+            ],
+        ];
+        for (&(z, q), y) in x.iter().zip(y.iter()) {
+            let values = [
+                (z.theta_1(q), z.theta_1_derivative(q)),
+                (z.theta_2(q), z.theta_2_derivative(q)),
+                (z.theta_3(q), z.theta_3_derivative(q)),
+                (z.theta_4(q), z.theta_4_derivative(q)),
+            ];
+            // The values for q close to 1 are limited by the rounding of ln q.
+            let tolerance = if q > 0.9 { 1e-13 } else { 1e-14 };
+            for (value, y) in values.iter().zip(y.iter()) {
+                assert::close(value.0, y.0, tolerance * y.0.abs());
+                assert::close(value.1, y.1, tolerance * y.1.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn elliptic_nome() {
+        let m: [f64; 8] = [0.0, 1e-12, 0.01, 0.3, 0.7, 0.99, 1.0 - 2f64.powi(-30), 1.0];
+        // References: mpmath.qfrom(m=m)
+        let q: [f64; 8] = [
+            0.0,
+            6.2500000000031249e-14,
+            0.0006281456603830156,
+            0.022277436157153507,
+            0.074689943537179439,
+            0.26219626791770933,
+            0.65784292115649949,
+            1.0,
+        ];
+        for (&m, &q) in m.iter().zip(q.iter()) {
+            assert::close(m.elliptic_nome(), q, 1e-15 * q);
+        }
+    }
+
+    #[test]
+    fn inv_elliptic_nome() {
+        let q: [f64; 5] = [1e-6, 0.01, 0.05, 0.3, 0.9];
+        // References: mpmath.mfrom(q=q)
+        let m: [f64; 5] = [
+            1.5999872000703996e-5,
+            0.14787439154361494,
+            0.55187034546696893,
+            0.99560436836083024,
+            1.0,
+        ];
+        for (&q, &m) in q.iter().zip(m.iter()) {
+            assert::close(q.inv_elliptic_nome(), m, 1e-15 * m);
+        }
+        for i in 1..100 {
+            let m = i as f64 / 100.0;
+            assert::close(m.elliptic_nome().inv_elliptic_nome(), m, 1e-15);
+        }
+    }
+}
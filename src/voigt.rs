@@ -0,0 +1,204 @@
+use num_complex::Complex;
+
+use crate::faddeeva::{faddeeva_grad, Faddeeva};
+
+/// Voigt functions.
+pub trait Voigt
+where
+    Self: Sized,
+{
+    /// Compute the Voigt profile, which is the convolution of a Gaussian with standard deviation
+    /// σ and a Lorentzian with half width at half maximum γ.
+    ///
+    /// The profile is computed as Re w(z) / (σ √(2π)) with z = (x + iγ) / (σ √2) where w is the
+    /// Faddeeva function. For σ negligible relative to |x + iγ|, the Lorentzian
+    /// γ / (π (x² + γ²)) is returned, and for γ = 0, the result is the Gaussian
+    /// exp(-x² / (2σ²)) / (σ √(2π)). The function is computed for `self` as x.
+    fn voigt(self, sigma: Self, gamma: Self) -> Self;
+
+    /// Compute the Voigt profile and its partial derivatives.
+    ///
+    /// The function returns V(x; σ, γ) together with ∂V/∂x, ∂V/∂σ, and ∂V/∂γ, which are obtained
+    /// via w′(z) = -2z w(z) + 2i / √π and, for σ, via w″(z) = -2 (w(z) + z w′(z)). For σ
+    /// negligible relative to |x + iγ|, the derivatives of the Lorentzian are returned, with
+    /// ∂V/∂σ = 0.
+    fn voigt_grad(self, sigma: Self, gamma: Self) -> (Self, Self, Self, Self);
+
+    /// Compute the Voigt line-shape function, H(a, u) = Re w(u + ia).
+    ///
+    /// The function is computed for `self` as u.
+    fn voigt_line_shape(self, a: Self) -> Self;
+}
+
+const FRAC_1_SQRT_2: f64 = core::f64::consts::FRAC_1_SQRT_2;
+const FRAC_1_SQRT_2PI: f64 = 0.398942280401432677939946059934381868;
+const FRAC_1_PI: f64 = core::f64::consts::FRAC_1_PI;
+
+// The ratio of σ to |x + iγ| below which the Lorentzian is exact to double precision.
+const LORENTZIAN_THRESHOLD: f64 = 1e-9;
+
+impl Voigt for f64 {
+    fn voigt(self, sigma: Self, gamma: Self) -> Self {
+        let x = self;
+        debug_assert!(sigma >= 0.0 && gamma >= 0.0);
+        let square = x * x + gamma * gamma;
+        if sigma * sigma <= LORENTZIAN_THRESHOLD * LORENTZIAN_THRESHOLD * square {
+            return FRAC_1_PI * gamma / square;
+        }
+        let scale = FRAC_1_SQRT_2 / sigma;
+        let z = Complex::new(x * scale, gamma * scale);
+        FRAC_1_SQRT_2PI / sigma * z.faddeeva().re
+    }
+
+    fn voigt_grad(self, sigma: Self, gamma: Self) -> (Self, Self, Self, Self) {
+        let x = self;
+        debug_assert!(sigma >= 0.0 && gamma >= 0.0);
+        let square = x * x + gamma * gamma;
+        if sigma * sigma <= LORENTZIAN_THRESHOLD * LORENTZIAN_THRESHOLD * square {
+            let value = FRAC_1_PI * gamma / square;
+            let factor = FRAC_1_PI / (square * square);
+            return (
+                value,
+                -2.0 * x * gamma * factor,
+                0.0,
+                (x - gamma) * (x + gamma) * factor,
+            );
+        }
+        let scale = FRAC_1_SQRT_2 / sigma;
+        let z = Complex::new(x * scale, gamma * scale);
+        let (w, dw, d2w) = faddeeva_grad(z);
+        let factor = FRAC_1_SQRT_2PI / sigma;
+        (
+            factor * w.re,
+            factor * scale * dw.re,
+            0.5 * factor / sigma * d2w.re,
+            -factor * scale * dw.im,
+        )
+    }
+
+    #[inline]
+    fn voigt_line_shape(self, a: Self) -> Self {
+        Complex::new(self, a).faddeeva().re
+    }
+}
+
+impl Voigt for f32 {
+    #[inline]
+    fn voigt(self, sigma: Self, gamma: Self) -> Self {
+        (self as f64).voigt(sigma as f64, gamma as f64) as f32
+    }
+
+    #[inline]
+    fn voigt_grad(self, sigma: Self, gamma: Self) -> (Self, Self, Self, Self) {
+        let (value, dx, dsigma, dgamma) = (self as f64).voigt_grad(sigma as f64, gamma as f64);
+        (value as f32, dx as f32, dsigma as f32, dgamma as f32)
+    }
+
+    #[inline]
+    fn voigt_line_shape(self, a: Self) -> Self {
+        (self as f64).voigt_line_shape(a as f64) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Voigt;
+
+    #[test]
+    fn voigt() {
+        let x: [(f64, f64, f64); 9] = [
+            (0.0, 1.0, 0.0),
+            (1.0, 1.0, 1.0),
+            (-2.5, 0.5, 0.1),
+            (10.0, 1.0, 0.001),
+            (300.0, 1.0, 0.5),
+            (0.1, 1e-12, 0.2),
+            (3.0, 0.0, 2.0),
+            (7.0, 0.5, 0.0),
+            (1.0, 2.0, 1000.0),
+        ];
+        // References:
+        // mpmath.re(w(z)) / (sigma * mpmath.sqrt(2 * mpmath.pi)) with z = (x + 1j * gamma) /
+        // (sigma * mpmath.sqrt(2)) and w(z) = mpmath.exp(-z**2) * mpmath.erfc(-1j * z)
+        let y: [f64; 9] = [
+            0.39894228040143268,
+            0.16579566268916646,
+            5.8732620500734325e-3,
+            3.2837345633831116e-6,
+            1.7684422934050658e-6,
+            1.2732395447351626,
+            0.04897075172058318,
+            2.1932131187779426e-43,
+            3.1830829465759582e-4,
+        ];
+        for i in 0..x.len() {
+            let (x, sigma, gamma) = x[i];
+            assert::close(x.voigt(sigma, gamma), y[i], 1e-14 * y[i]);
+        }
+    }
+
+    #[test]
+    fn voigt_grad() {
+        let x: [(f64, f64, f64); 9] = [
+            (0.0, 1.0, 0.0),
+            (1.0, 1.0, 1.0),
+            (-2.5, 0.5, 0.1),
+            (10.0, 1.0, 0.001),
+            (300.0, 1.0, 0.5),
+            (0.1, 1e-12, 0.2),
+            (3.0, 0.0, 2.0),
+            (7.0, 0.5, 0.0),
+            (1.0, 2.0, 1000.0),
+        ];
+        // References: mpmath.diff of the above
+        let y: [(f64, f64, f64); 9] = [
+            (0.0, -0.39894228040143268, -core::f64::consts::FRAC_1_PI),
+            (
+                -0.073911361420436892,
+                -0.031254379042834915,
+                -0.06062992222589465,
+            ),
+            (
+                5.5608440337010451e-3,
+                4.3768617162186375e-3,
+                0.058404171760698611,
+            ),
+            (
+                -6.7798389456618932e-7,
+                2.1236988968563446e-7,
+                3.2837344925931471e-3,
+            ),
+            (
+                -1.1789975553176963e-8,
+                1.1790455929307634e-10,
+                3.53686493597746e-6,
+            ),
+            (-5.0929581789406502, 0.0, -3.8197186342054876),
+            (-0.022601885409499929, 0.0, 9.4174522539583039e-3),
+            (
+                -6.1409967325782393e-42,
+                8.5535311632339762e-41,
+                6.5981820252097722e-3,
+            ),
+            (
+                -6.3660322079014865e-10,
+                -1.2732013489429559e-9,
+                -3.1830511165167714e-7,
+            ),
+        ];
+        for i in 0..x.len() {
+            let (x, sigma, gamma) = x[i];
+            let (_, dx, dsigma, dgamma) = x.voigt_grad(sigma, gamma);
+            assert::close(dx, y[i].0, 1e-13 * y[i].0.abs());
+            assert::close(dsigma, y[i].1, 1e-12 * y[i].1.abs());
+            assert::close(dgamma, y[i].2, 1e-13 * y[i].2.abs());
+        }
+    }
+
+    #[test]
+    fn voigt_line_shape() {
+        // References: mpmath.re(w(u + 1j * a))
+        assert::close(1.5f64.voigt_line_shape(0.2), 0.1565205841887955, 1e-15);
+        assert::close(20.0f64.voigt_line_shape(1e-3), 1.415796583184698e-6, 1e-20);
+    }
+}
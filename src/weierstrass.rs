@@ -0,0 +1,547 @@
+#![allow(unstable_name_collisions)]
+
+#[cfg(feature = "complex")]
+use num_complex::Complex;
+
+#[allow(unused_imports)]
+use crate::primitive::Primitive;
+use crate::{Elliptic, Jacobi, Theta};
+
+/// Weierstrass elliptic functions.
+///
+/// The functions are defined by the invariants g₂ and g₃, which are real, and are computed for
+/// real arguments. The roots e₁, e₂, and e₃ of 4t³ - g₂t - g₃ are found in closed form. For a
+/// nonnegative discriminant g₂³ - 27g₃², the roots are real with e₁ ≥ e₂ ≥ e₃, the period lattice
+/// is rectangular, and the functions are reduced to the Jacobi elliptic functions with the
+/// parameter m = (e₂ - e₃) / (e₁ - e₃). For a negative discriminant, e₂ is the only real root, the
+/// lattice is rhombic, and the parameter is m = 1/2 - 3e₂ / (4H) with H² = (e₂ - e₁)(e₂ - e₃). The
+/// functions ζ and σ are computed via the Jacobi theta functions.
+pub trait Weierstrass: Sized {
+    /// Compute the Weierstrass elliptic function ℘(z; g₂, g₃).
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: argument (z)
+    /// - `g2` and `g3`: invariants (g₂ and g₃)
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use special::Weierstrass;
+    ///
+    /// let z = 0.5;
+    /// assert::close(z.weierstrass_p(2.0, 1.0), 4.027288182407718, 1e-14);
+    /// ```
+    fn weierstrass_p(self, g2: Self, g3: Self) -> Self;
+
+    /// Compute the derivative of the Weierstrass elliptic function, ℘′(z; g₂, g₃).
+    fn weierstrass_p_derivative(self, g2: Self, g3: Self) -> Self;
+
+    /// Compute the Weierstrass zeta function, ζ(z; g₂, g₃), which satisfies ζ′ = -℘.
+    fn weierstrass_zeta(self, g2: Self, g3: Self) -> Self;
+
+    /// Compute the Weierstrass sigma function, σ(z; g₂, g₃), which satisfies σ′ / σ = ζ.
+    fn weierstrass_sigma(self, g2: Self, g3: Self) -> Self;
+
+    /// Compute the half-periods of the Weierstrass elliptic functions with the invariants g₂
+    /// and g₃.
+    ///
+    /// The function returns the real half-period ω and the complex half-period ω′, so that 2ω and
+    /// 2ω′ generate the period lattice. For a positive discriminant, ω = RF(0, e₁ - e₂, e₁ - e₃)
+    /// and ω′ = i RF(0, e₁ - e₃, e₂ - e₃) where RF is Carlson’s symmetric integral of the first
+    /// kind. For a negative discriminant, ω = RF(0, H(1 - m), H) and ω′ = (ω + i RF(0, Hm, H)) / 2.
+    /// For a zero discriminant, one of the half-periods is infinite. The function is available
+    /// only with the `complex` feature.
+    ///
+    /// ## Parameters
+    ///
+    /// - `self`: invariant (g₂)
+    /// - `g3`: invariant (g₃)
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use special::Weierstrass;
+    ///
+    /// let (real, complex) = 2.0.weierstrass_half_periods(1.0);
+    /// assert::close(real, 1.3513123906549478, 1e-15);
+    /// assert::close(complex.re, 0.6756561953274739, 1e-15);
+    /// assert::close(complex.im, 1.547919996893984, 1e-15);
+    /// ```
+    #[cfg(feature = "complex")]
+    fn weierstrass_half_periods(self, g3: Self) -> (Self, Complex<Self>);
+}
+
+const PI: f64 = core::f64::consts::PI;
+
+// The period lattice in terms of the roots of 4t³ - g₂t - g₃ and the parameter m of the Jacobi
+// elliptic functions, which are evaluated at u = z √λ.
+enum Lattice {
+    // g₂ = g₃ = 0, in which case ℘(z) = 1 / z².
+    Degenerate,
+    // ℘(z) = e₃ + λ ns²(u) with λ = e₁ - e₃.
+    Rectangular {
+        e1: f64,
+        e3: f64,
+        lambda: f64,
+        m: f64,
+        complement: f64,
+    },
+    // ℘(z) = e₂ + λ cs²(u) nd²(u) with λ = H.
+    Rhombic {
+        e2: f64,
+        lambda: f64,
+        m: f64,
+        complement: f64,
+    },
+}
+
+impl Lattice {
+    fn new(g2: f64, g3: f64) -> Self {
+        let discriminant = g2 * g2 * g2 - 27.0 * g3 * g3;
+        if discriminant > 0.0 {
+            // The roots are 2 √(g₂ / 12) cos(α - 2πk / 3) with cos 3α = 3√3 g₃ / g₂^(3/2), and
+            // the middle one, which is the smallest in magnitude, is recovered via
+            // e₁e₂e₃ = g₃ / 4.
+            let radius = (g2 / 12.0).sqrt();
+            let cosine = (3.0 * 3f64.sqrt() * g3 / (g2 * g2.sqrt())).clamp(-1.0, 1.0);
+            let alpha = cosine.acos() / 3.0;
+            let e1 = 2.0 * radius * alpha.cos();
+            let e3 = 2.0 * radius * (alpha - 4.0 * PI / 3.0).cos();
+            let e2 = g3 / (4.0 * e1 * e3);
+            Self::rectangular(e1, e2, e3)
+        } else if discriminant == 0.0 {
+            if g2 == 0.0 {
+                return Lattice::Degenerate;
+            }
+            // The roots are 3g₃ / g₂ and -3g₃ / (2g₂), the latter being double.
+            let (simple, double) = (3.0 * g3 / g2, -1.5 * g3 / g2);
+            if simple > double {
+                Self::rectangular(simple, double, double)
+            } else {
+                Self::rectangular(double, double, simple)
+            }
+        } else {
+            // The real root is found via Cardano’s formula with the two cube roots combined
+            // without cancellation, and H² = 3e₂² - g₂ / 4.
+            let root = (-discriminant / 1728.0).sqrt();
+            let a = (0.125 * g3.abs() + root).powf(1.0 / 3.0);
+            let a = if g3 < 0.0 { -a } else { a };
+            let e2 = a + g2 / (12.0 * a);
+            let lambda = (3.0 * e2 * e2 - 0.25 * g2).sqrt();
+            // m = (H - 3e₂ / 2) / (2H) and 1 - m = (H + 3e₂ / 2) / (2H) with the difference
+            // evaluated via H² - 9e₂² / 4 = 3e₂² / 4 - g₂ / 4.
+            let square = 0.75 * e2 * e2 - 0.25 * g2;
+            let (m, complement) = if e2 > 0.0 {
+                let m = square / (2.0 * lambda * (lambda + 1.5 * e2));
+                (m, 1.0 - m)
+            } else {
+                let complement = square / (2.0 * lambda * (lambda - 1.5 * e2));
+                (1.0 - complement, complement)
+            };
+            Lattice::Rhombic {
+                e2,
+                lambda,
+                m,
+                complement,
+            }
+        }
+    }
+
+    fn rectangular(e1: f64, e2: f64, e3: f64) -> Self {
+        let lambda = e1 - e3;
+        Lattice::Rectangular {
+            e1,
+            e3,
+            lambda,
+            m: (e2 - e3) / lambda,
+            complement: (e1 - e2) / lambda,
+        }
+    }
+
+    // Compute ℘(z) and ℘′(z).
+    fn p(&self, z: f64) -> (f64, f64) {
+        match *self {
+            Lattice::Degenerate => (1.0 / (z * z), -2.0 / (z * z * z)),
+            Lattice::Rectangular { e3, lambda, m, .. } => {
+                let (sn, cn, dn) = m.jacobi_elliptic(z * lambda.sqrt());
+                let ns = 1.0 / sn;
+                (
+                    e3 + lambda * ns * ns,
+                    -2.0 * lambda * lambda.sqrt() * cn * dn * ns * ns * ns,
+                )
+            }
+            Lattice::Rhombic { e2, lambda, m, .. } => {
+                let (sn, cn, dn) = m.jacobi_elliptic(z * lambda.sqrt());
+                let denominator = 1.0 / (sn * dn);
+                let value = cn * denominator;
+                (
+                    e2 + lambda * value * value,
+                    2.0 * lambda
+                        * lambda.sqrt()
+                        * value
+                        * (m * sn * sn * cn * cn - dn * dn)
+                        * denominator
+                        * denominator,
+                )
+            }
+        }
+    }
+
+    // Compute ζ(z) and σ(z).
+    fn zeta_sigma(&self, z: f64) -> (f64, f64) {
+        match *self {
+            Lattice::Degenerate => (1.0 / z, z),
+            Lattice::Rectangular {
+                e1,
+                e3,
+                lambda,
+                m,
+                complement,
+            } => {
+                let root = lambda.sqrt();
+                let w = z * root;
+                if m == 0.0 {
+                    // ζ(z) = -e₃z + √λ cot w and σ(z) = exp(-e₃z² / 2) sin w / √λ
+                    let (sin, cos) = (w.sin(), w.cos());
+                    (
+                        -e3 * z + root * cos / sin,
+                        (-0.5 * e3 * z * z).exp() * sin / root,
+                    )
+                } else if complement == 0.0 {
+                    // ζ(z) = -e₁z + √λ coth w and σ(z) = exp(-e₁z² / 2) sinh w / √λ
+                    let tanh = w.tanh();
+                    (
+                        -e1 * z + root / tanh,
+                        (-0.5 * e1 * z * z).exp() * tanh * w.cosh() / root,
+                    )
+                } else {
+                    let (k, k_complement) = (quarter_period(complement), quarter_period(m));
+                    let c = e1 - lambda * m.elliptic_e() / k;
+                    theta_zeta_sigma(z, root, c, k, k_complement, false)
+                }
+            }
+            Lattice::Rhombic {
+                e2,
+                lambda,
+                m,
+                complement,
+            } => {
+                let (k, k_complement) = (quarter_period(complement), quarter_period(m));
+                let c = e2 + lambda * (1.0 - 2.0 * m.elliptic_e() / k);
+                theta_zeta_sigma(z, lambda.sqrt(), c, k, k_complement, true)
+            }
+        }
+    }
+
+    // Compute the real half-period and the complex one.
+    #[cfg(feature = "complex")]
+    fn half_periods(&self) -> (f64, Complex<f64>) {
+        match *self {
+            Lattice::Degenerate => (f64::INFINITY, Complex::new(0.0, f64::INFINITY)),
+            Lattice::Rectangular {
+                lambda,
+                m,
+                complement,
+                ..
+            } => (
+                rf(lambda * complement, lambda),
+                Complex::new(0.0, rf(lambda * m, lambda)),
+            ),
+            Lattice::Rhombic {
+                lambda,
+                m,
+                complement,
+                ..
+            } => {
+                let real = rf(lambda * complement, lambda);
+                (real, Complex::new(0.5 * real, 0.5 * rf(lambda * m, lambda)))
+            }
+        }
+    }
+}
+
+// Compute RF(0, x, y), which is infinite if x = 0.
+fn rf(x: f64, y: f64) -> f64 {
+    if x == 0.0 {
+        f64::INFINITY
+    } else {
+        0.0.elliptic_rf(x, y)
+    }
+}
+
+// Compute the complete elliptic integral of the first kind from the complementary parameter as
+// K = RF(0, 1 - m, 1).
+fn quarter_period(complement: f64) -> f64 {
+    rf(complement, 1.0)
+}
+
+// Compute ζ(z) and σ(z) via ζ(z) = -cz + d/dz ln Θ(v) and σ(z) = exp(-cz² / 2) Θ(v) / Θ′(0) with
+// v = πz√λ / (2K) and q = exp(-πK′ / K) where Θ is θ₁ for the rectangular lattice and θ₁θ₃ for the
+// rhombic one.
+fn theta_zeta_sigma(
+    z: f64,
+    root: f64,
+    c: f64,
+    k: f64,
+    k_complement: f64,
+    rhombic: bool,
+) -> (f64, f64) {
+    let q = (-PI * k_complement / k).exp();
+    let scale = 0.5 * PI * root / k;
+    let v = scale * z;
+    let (mut value, mut logarithmic) = (v.theta_1(q), v.theta_1_derivative(q));
+    logarithmic /= value;
+    let mut normalization = 0.0.theta_1_derivative(q);
+    if rhombic {
+        let (third, derivative) = (v.theta_3(q), v.theta_3_derivative(q));
+        value *= third;
+        logarithmic += derivative / third;
+        normalization *= 0.0.theta_3(q);
+    }
+    (
+        -c * z + scale * logarithmic,
+        (-0.5 * c * z * z).exp() * value / (scale * normalization),
+    )
+}
+
+impl Weierstrass for f64 {
+    #[inline]
+    fn weierstrass_p(self, g2: Self, g3: Self) -> Self {
+        Lattice::new(g2, g3).p(self).0
+    }
+
+    #[inline]
+    fn weierstrass_p_derivative(self, g2: Self, g3: Self) -> Self {
+        Lattice::new(g2, g3).p(self).1
+    }
+
+    #[inline]
+    fn weierstrass_zeta(self, g2: Self, g3: Self) -> Self {
+        Lattice::new(g2, g3).zeta_sigma(self).0
+    }
+
+    #[inline]
+    fn weierstrass_sigma(self, g2: Self, g3: Self) -> Self {
+        Lattice::new(g2, g3).zeta_sigma(self).1
+    }
+
+    #[cfg(feature = "complex")]
+    #[inline]
+    fn weierstrass_half_periods(self, g3: Self) -> (Self, Complex<Self>) {
+        Lattice::new(self, g3).half_periods()
+    }
+}
+
+impl Weierstrass for f32 {
+    #[inline]
+    fn weierstrass_p(self, g2: Self, g3: Self) -> Self {
+        (self as f64).weierstrass_p(g2 as f64, g3 as f64) as f32
+    }
+
+    #[inline]
+    fn weierstrass_p_derivative(self, g2: Self, g3: Self) -> Self {
+        (self as f64).weierstrass_p_derivative(g2 as f64, g3 as f64) as f32
+    }
+
+    #[inline]
+    fn weierstrass_zeta(self, g2: Self, g3: Self) -> Self {
+        (self as f64).weierstrass_zeta(g2 as f64, g3 as f64) as f32
+    }
+
+    #[inline]
+    fn weierstrass_sigma(self, g2: Self, g3: Self) -> Self {
+        (self as f64).weierstrass_sigma(g2 as f64, g3 as f64) as f32
+    }
+
+    #[cfg(feature = "complex")]
+    #[inline]
+    fn weierstrass_half_periods(self, g3: Self) -> (Self, Complex<Self>) {
+        let (real, complex) = (self as f64).weierstrass_half_periods(g3 as f64);
+        (
+            real as f32,
+            Complex::new(complex.re as f32, complex.im as f32),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Weierstrass;
+
+    #[test]
+    fn weierstrass() {
+        let x: [(f64, f64, f64); 14] = [
+            (0.7, 2.0, 1.0),
+            (1.1, 2.0, 1.0),
+            (0.7, 4.0, 1.0),
+            (1.1, 4.0, 1.0),
+            (0.7, 1.0, -2.0),
+            (1.1, 1.0, -2.0),
+            (0.7, 10.0, 2.0),
+            (1.1, 10.0, 2.0),
+            (0.7, -3.0, 1.0),
+            (1.1, -3.0, 1.0),
+            (0.7, 3.0, 1.0),
+            (1.1, 3.0, 1.0),
+            (0.7, 3.0, -1.0),
+            (1.1, 3.0, -1.0),
+        ];
+        // References: mpmath with the Laurent series ℘(z) = 1 / z² + Σ cₖ z^(2k - 2) where
+        // c₂ = g₂ / 20, c₃ = g₃ / 28, and cₖ = 3 / ((2k + 1)(k - 3)) Σ cⱼ cₖ₋ⱼ, and the series of
+        // ζ(z) and ln σ(z) obtained by integration
+        let y: [[f64; 4]; 14] = [
+            [
+                2.0988441887472748,
+                -5.637834668514701,
+                1.4158937256274198,
+                0.69850035694192781,
+            ],
+            [
+                1.0082021259892768,
+                -1.0405915459629482,
+                0.8519916631882867,
+                1.0841980500980031,
+            ],
+            [
+                2.1490879762408842,
+                -5.4869462904421771,
+                1.404337677365682,
+                0.69709675403790237,
+            ],
+            [
+                1.1502115849482128,
+                -0.69714570297334638,
+                0.80447053375275403,
+                1.070599395721695,
+            ],
+            [
+                2.04821937204319,
+                -5.8585460256923505,
+                1.4252496143587462,
+                0.6994955562921288,
+            ],
+            [
+                0.78277385348095796,
+                -1.7708070475596111,
+                0.90983616080141193,
+                1.0979168218878196,
+            ],
+            [
+                2.3135430729027755,
+                -4.9393679478223205,
+                1.3679664609209906,
+                0.69277581476467828,
+            ],
+            [
+                1.7283517424874885,
+                1.1697051415172259,
+                0.63625309399753795,
+                1.0267723374043409,
+            ],
+            [
+                1.9766880150517301,
+                -5.985322118281141,
+                1.4444393416124729,
+                0.70200060835059712,
+            ],
+            [
+                0.70734097012116576,
+                -1.5929978254116758,
+                0.96243919122948622,
+                1.1176835096353624,
+            ],
+            [
+                2.1238663178938908,
+                -5.5632557836990424,
+                1.4101256142051709,
+                0.69779880557657243,
+            ],
+            [
+                1.0775513240443824,
+                -0.87863419570529445,
+                0.82848038358990827,
+                1.0774132992466935,
+            ],
+            [
+                2.1065465078949306,
+                -5.6632042861667225,
+                1.412539789024614,
+                0.69799494072557061,
+            ],
+            [
+                0.96638922146556471,
+                -1.3080165626249212,
+                0.85228000822430774,
+                1.0820608065539469,
+            ],
+        ];
+        for (&(z, g2, g3), y) in x.iter().zip(y.iter()) {
+            assert::close(z.weierstrass_p(g2, g3), y[0], 1e-14 * y[0].abs());
+            assert::close(z.weierstrass_p_derivative(g2, g3), y[1], 1e-14 * y[1].abs());
+            assert::close(z.weierstrass_zeta(g2, g3), y[2], 1e-14 * y[2].abs());
+            assert::close(z.weierstrass_sigma(g2, g3), y[3], 1e-14 * y[3].abs());
+        }
+    }
+
+    #[test]
+    fn weierstrass_degenerate() {
+        let z = 0.8f64;
+        assert::close(z.weierstrass_p(0.0, 0.0), 1.0 / (z * z), 1e-15);
+        assert::close(
+            z.weierstrass_p_derivative(0.0, 0.0),
+            -2.0 / (z * z * z),
+            1e-15,
+        );
+        assert::close(z.weierstrass_zeta(0.0, 0.0), 1.0 / z, 1e-15);
+        assert::close(z.weierstrass_sigma(0.0, 0.0), z, 1e-15);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn weierstrass_half_periods() {
+        let x: [(f64, f64); 4] = [(2.0, 1.0), (4.0, 1.0), (1.0, -2.0), (-3.0, 1.0)];
+        // References: mpmath.elliprf(0, e₁ - e₂, e₁ - e₃) and mpmath.elliprf(0, e₁ - e₃, e₂ - e₃)
+        // with the roots given by mpmath.polyroots
+        let y: [(f64, f64, f64); 4] = [
+            (1.3513123906549478, 0.6756561953274739, 1.547919996893984),
+            (1.225694690993395, 0.0, 1.4967293231159798),
+            (2.4587375032658302, 1.2293687516329151, 0.65418238131854843),
+            (1.6944702783481073, 0.84723513917405365, 1.0500353672047947),
+        ];
+        for (&(g2, g3), &(real, re, im)) in x.iter().zip(y.iter()) {
+            let (value, complex) = g2.weierstrass_half_periods(g3);
+            assert::close(value, real, 1e-15 * real);
+            assert::close(complex.re, re, 1e-15 * re);
+            assert::close(complex.im, im, 1e-15 * im);
+        }
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn weierstrass_quasi_periodicity() {
+        for &(g2, g3) in [(2.0f64, 1.0), (4.0, 1.0), (-3.0, 1.0)].iter() {
+            let (omega, _) = g2.weierstrass_half_periods(g3);
+            let eta = omega.weierstrass_zeta(g2, g3);
+            for &z in [0.3, -0.9, 1.7].iter() {
+                let shifted = z + 2.0 * omega;
+                assert::close(
+                    shifted.weierstrass_p(g2, g3),
+                    z.weierstrass_p(g2, g3),
+                    1e-12 * z.weierstrass_p(g2, g3).abs(),
+                );
+                assert::close(
+                    shifted.weierstrass_zeta(g2, g3),
+                    z.weierstrass_zeta(g2, g3) + 2.0 * eta,
+                    1e-12,
+                );
+                let sigma = -(2.0 * eta * (z + omega)).exp() * z.weierstrass_sigma(g2, g3);
+                assert::close(
+                    shifted.weierstrass_sigma(g2, g3),
+                    sigma,
+                    1e-12 * sigma.abs(),
+                );
+            }
+        }
+    }
+}